@@ -1,9 +1,12 @@
-use std::collections::HashMap;
+#![allow(clippy::needless_return, clippy::redundant_static_lifetimes, clippy::redundant_field_names, clippy::collapsible_if, clippy::collapsible_else_if)]
+
+use std::collections::{HashMap,HashSet};
 use std::env;
-use clap;
+use chrono::{NaiveDate,NaiveDateTime};
 use float_cmp::{ApproxEq,F32Margin};
 use lazy_static::lazy_static;
 use regex::Regex;
+use serde::Deserialize;
 
 const GSETUP_VERSION: &'static str = "4.70; 2020-06-29; GCT";
 const GFIT_VERSION: &'static str = "5.28; 2020-04-24; GCT";
@@ -13,8 +16,102 @@ const AVERAGE_VERSION: &'static str = "1.37; 2020-07-31; GCT,JLL";
 const INSITU_VERSION: &'static str = "1.39; 2020-07-31; GCT,JLL";
 const WRITE_NC_HASH: &'static str = "42ed12d";
 
+// The expectations for each frozen GGG2020 release, selected with --release. The first is the default.
+#[derive(Debug)]
+struct Release {
+    name: &'static str,
+    gsetup_version: &'static str,
+    gfit_version: &'static str,
+    collate_version: &'static str,
+    airmass_version: &'static str,
+    average_version: &'static str,
+    insitu_version: &'static str,
+    write_nc_hash: &'static str,
+    adcf_table: &'static str,
+    aicf_table: &'static str,
+    windows_table: &'static str
+}
+
+static RELEASES: [Release; 1] = [
+    Release{
+        name: "ggg2020",
+        gsetup_version: GSETUP_VERSION,
+        gfit_version: GFIT_VERSION,
+        collate_version: COLLATE_VERSION,
+        airmass_version: AIRMASS_VERSION,
+        average_version: AVERAGE_VERSION,
+        insitu_version: INSITU_VERSION,
+        write_nc_hash: WRITE_NC_HASH,
+        adcf_table: ADCF_TABLE,
+        aicf_table: AICF_TABLE,
+        windows_table: WINDOWS_TABLE
+    },
+];
+
 const ATT_MISSING_STR: &'static str = "!!MISSING!!";
 
+// Version of the --sqlite tables and --prometheus series; increment when either changes incompatibly
+const REPORT_SCHEMA_VERSION: i64 = 1;
+
+// ****** //
+// ERRORS //
+// ****** //
+
+#[derive(Debug)]
+enum CheckError {
+    // (path, underlying error)
+    FileOpen(String, String),
+    FileRead(String, String),
+    FileWrite(String, String),
+    // variable name
+    VariableMissing(String),
+    // (variable name, underlying error)
+    VariableRead(String, String),
+    // attribute name
+    AttributeMissing(String),
+    // (attribute name, underlying error)
+    AttributeRead(String, String),
+    // (attribute name, what type it should have been)
+    AttributeType(String, &'static str),
+    // (table line, what was wrong with it)
+    TableParse(String, String),
+    // (database path, what went wrong)
+    Database(String, String),
+    // (first variable, its length, second variable, its length)
+    LengthMismatch(String, usize, String, usize),
+    Other(String)
+}
+
+impl std::fmt::Display for CheckError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            CheckError::FileOpen(path, err) => write!(f, "Unable to open {}: {}", path, err),
+            CheckError::FileRead(path, err) => write!(f, "Unable to read {}: {}", path, err),
+            CheckError::FileWrite(path, err) => write!(f, "Unable to write {}: {}", path, err),
+            CheckError::VariableMissing(name) => write!(f, "Could not read variable '{}'", name),
+            CheckError::VariableRead(name, err) => write!(f, "Could not get data of '{}' variable: {}", name, err),
+            CheckError::AttributeMissing(name) => write!(f, "Attribute '{}' is not present", name),
+            CheckError::AttributeRead(name, err) => write!(f, "Could not get value for attribute '{}': {}", name, err),
+            CheckError::AttributeType(name, expected) => write!(f, "Attribute '{}' has an unexpected type (expected {})", name, expected),
+            CheckError::TableParse(line, reason) => write!(f, "Could not parse table line '{}': {}", line, reason),
+            CheckError::Database(path, reason) => write!(f, "Database {}: {}", path, reason),
+            CheckError::LengthMismatch(name_a, len_a, name_b, len_b) => write!(f, "{} and {} have different lengths ({} vs. {})", name_a, name_b, len_a, len_b),
+            CheckError::Other(msg) => write!(f, "{}", msg)
+        }
+    }
+}
+
+impl std::error::Error for CheckError {}
+
+fn _check_same_length(name_a: &str, len_a: usize, name_b: &str, len_b: usize) -> Result<(), CheckError> {
+    // For checks that compare two variables record by record
+    if len_a != len_b {
+        return Err(CheckError::LengthMismatch(String::from(name_a), len_a, String::from(name_b), len_b));
+    }
+    Ok(())
+}
+
+
 const ADCF_TABLE: &'static str = " Gas         ADCF      ADCF_Err  g    p
 \"xco2_6220\"  -0.00903  0.00025   15   4
 \"xco2_6339\"  -0.00512  0.00025   45   5
@@ -32,8 +129,9 @@ const ADCF_TABLE: &'static str = " Gas         ADCF      ADCF_Err  g    p
 \"xluft_6146\"  0.00053  0.00017  -45   1";
 
 #[derive(Debug)]
+#[allow(dead_code)]
 struct Adcf {
-    window: &'static str,
+    window: String,
     adcf: f32,
     err: f32,
     g: i32,
@@ -41,28 +139,51 @@ struct Adcf {
 }
 
 
-fn read_adcf_table() -> HashMap<&'static str, Adcf> {
+fn read_adcf_table(release: &Release) -> HashMap<String, Adcf> {
+    // The embedded table is fixed at compile time, so failing to parse it is a bug
+    return parse_adcf_table(release.adcf_table).unwrap();
+}
+
+fn parse_adcf_table(table: &str) -> Result<HashMap<String, Adcf>, CheckError> {
     let mut adcfs = HashMap::new();
     let mut first_line = true;
-    for line in ADCF_TABLE.split("\n") {
+    for line in table.split("\n") {
         if first_line {
             first_line = false;
             continue;
+        }else if line.trim().is_empty() {
+            continue;
+        }
+
+        let parts: Vec<&str> = line.split_whitespace().collect();
+        if parts.len() < 5 {
+            return Err(CheckError::TableParse(String::from(line), String::from("expected 5 columns in the ADCF table")));
         }
 
-        let parts: Vec<&'static str> = line.split_whitespace().into_iter().collect();
-        let window = parts[0].strip_prefix('"').unwrap().strip_suffix('"').unwrap();
+        let window = parts[0].trim_matches('"');
         let s = Adcf{
-            window: window, 
-            adcf: parts[1].parse::<f32>().unwrap(), 
-            err: parts[2].parse::<f32>().unwrap(), 
-            g: parts[3].parse::<i32>().unwrap(), 
-            p: parts[4].parse::<i32>().unwrap()
+            window: String::from(window), 
+            adcf: _parse_table_value(parts[1], line)?, 
+            err: _parse_table_value(parts[2], line)?, 
+            g: _parse_table_value(parts[3], line)?, 
+            p: _parse_table_value(parts[4], line)?
         };
-        adcfs.insert(window, s);
+        adcfs.insert(String::from(window), s);
     }
 
-    return adcfs;
+    return Ok(adcfs);
+}
+
+fn _parse_table_value<T: std::str::FromStr>(value: &str, line: &str) -> Result<T, CheckError> {
+    // A table edited with a comma-decimal locale would otherwise just fail to parse with no hint why
+    if value.contains(',') {
+        return Err(CheckError::TableParse(String::from(line), format!("value '{}' contains a comma; values must use '.' as the decimal separator (e.g. 1.0083, not 1,0083)", value)));
+    }
+
+    match value.parse::<T>() {
+        Ok(v) => Ok(v),
+        Err(_) => Err(CheckError::TableParse(String::from(line), format!("could not parse value '{}'", value)))
+    }
 }
 
 
@@ -78,33 +199,227 @@ const AICF_TABLE: &'static str = " Gas     AICF  AICF_Err  WMO_Scale
 
 
 #[derive(Debug)]
+#[allow(dead_code)]
 struct Aicf {
-    gas: &'static str,
+    gas: String,
     aicf: f32,
     err: f32
 }
 
 
-fn read_aicf_table() -> HashMap<&'static str, Aicf> {
+fn read_aicf_table(release: &Release) -> HashMap<String, Aicf> {
+    // As with the ADCFs, the embedded table must always parse
+    return parse_aicf_table(release.aicf_table).unwrap();
+}
+
+fn parse_aicf_table(table: &str) -> Result<HashMap<String, Aicf>, CheckError> {
     let mut aicfs = HashMap::new();
     let mut first_line = true;
-    for line in AICF_TABLE.split("\n") {
+    for line in table.split("\n") {
         if first_line {
             first_line = false;
             continue;
+        }else if line.trim().is_empty() {
+            continue;
         }
 
-        let parts: Vec<&'static str> = line.split_whitespace().into_iter().collect();
-        let gas = parts[0].strip_prefix('"').unwrap().strip_suffix('"').unwrap();
+        let parts: Vec<&str> = line.split_whitespace().collect();
+        if parts.len() < 3 {
+            return Err(CheckError::TableParse(String::from(line), String::from("expected at least 3 columns in the AICF table")));
+        }
+
+        let gas = parts[0].trim_matches('"');
         let s = Aicf{
-            gas: gas, 
-            aicf: parts[1].parse::<f32>().unwrap(), 
-            err: parts[2].parse::<f32>().unwrap(), 
+            gas: String::from(gas), 
+            aicf: _parse_table_value(parts[1], line)?, 
+            err: _parse_table_value(parts[2], line)?, 
         };
-        aicfs.insert(gas, s);
+        aicfs.insert(String::from(gas), s);
+    }
+
+    return Ok(aicfs);
+}
+
+
+// **************** //
+// REFERENCE TABLES //
+// **************** //
+
+fn load_correction_tables(clargs: &CmdLineArgs) -> (HashMap<String, Adcf>, HashMap<String, Aicf>) {
+    let base_url = match &clargs.reference_url {
+        Some(url) => url,
+        None => return (read_adcf_table(clargs.release), read_aicf_table(clargs.release))
+    };
+
+    let adcfs = match fetch_reference_table(base_url, "adcf.txt", clargs).and_then(|t| parse_adcf_table(&t)) {
+        Ok(t) => t,
+        Err(err) => {
+            if clargs.verbosity >= 0 {
+                eprintln!("WARNING: {}. Falling back to the embedded ADCF table.", err);
+            }
+            read_adcf_table(clargs.release)
+        }
+    };
+
+    let aicfs = match fetch_reference_table(base_url, "aicf.txt", clargs).and_then(|t| parse_aicf_table(&t)) {
+        Ok(t) => t,
+        Err(err) => {
+            if clargs.verbosity >= 0 {
+                eprintln!("WARNING: {}. Falling back to the embedded AICF table.", err);
+            }
+            read_aicf_table(clargs.release)
+        }
+    };
+
+    return (adcfs, aicfs);
+}
+
+fn fetch_reference_table(base_url: &str, table_name: &str, clargs: &CmdLineArgs) -> Result<String, CheckError> {
+    let url = format!("{}/{}", base_url.trim_end_matches('/'), table_name);
+    let cache_file = _reference_cache_dir().map(|d| d.join(_cache_file_name(&url)));
+
+    let response = ureq::get(&url)
+        .timeout(std::time::Duration::from_secs(30))
+        .call()
+        .map_err(|e| e.to_string())
+        .and_then(|r| r.into_string().map_err(|e| e.to_string()));
+
+    match response {
+        Ok(table) => {
+            if let Some(cache_file) = cache_file {
+                let written = cache_file.parent().map_or(Ok(()), std::fs::create_dir_all)
+                    .and_then(|_| std::fs::write(&cache_file, &table));
+                if let Err(err) = written {
+                    if clargs.verbosity >= 0 {
+                        eprintln!("WARNING: could not cache {} to {}: {}", url, cache_file.display(), err);
+                    }
+                }
+            }
+            Ok(table)
+        },
+        Err(err) => {
+            // Offline is a common case on processing nodes, so the last good copy
+            // is preferred to the embedded table when we have one
+            if let Some(cache_file) = cache_file {
+                if let Ok(table) = std::fs::read_to_string(&cache_file) {
+                    if clargs.verbosity >= 0 {
+                        eprintln!("WARNING: could not fetch reference table ({}), using the cached copy from {}", err, cache_file.display());
+                    }
+                    return Ok(table);
+                }
+            }
+            Err(CheckError::Other(format!("Could not fetch reference table ({})", err)))
+        }
+    }
+}
+
+fn _reference_cache_dir() -> Option<std::path::PathBuf> {
+    let base = match env::var_os("XDG_CACHE_HOME") {
+        Some(d) => std::path::PathBuf::from(d),
+        None => std::path::PathBuf::from(env::var_os("HOME")?).join(".cache")
+    };
+    return Some(base.join("check-phase2"));
+}
+
+fn _cache_file_name(url: &str) -> String {
+    url.chars().map(|c| if c.is_ascii_alphanumeric() || c == '.' {c} else {'_'}).collect()
+}
+
+fn emit_tables(out_dir: &str, clargs: &CmdLineArgs) -> Result<(), CheckError> {
+    // Written exactly as embedded so that the files parse the same way. adcf.txt and aicf.txt can be
+    // edited into overrides for --reference-url; windows.txt has no override, so is only for reference.
+    std::fs::create_dir_all(out_dir)
+        .map_err(|e| CheckError::FileWrite(String::from(out_dir), e.to_string()))?;
+
+    let release = clargs.release;
+    for (file_name, table) in [("adcf.txt", release.adcf_table), ("aicf.txt", release.aicf_table), ("windows.txt", release.windows_table)].iter() {
+        let out_file = std::path::Path::new(out_dir).join(file_name);
+        std::fs::write(&out_file, table)
+            .map_err(|e| CheckError::FileWrite(out_file.display().to_string(), e.to_string()))?;
+        if clargs.verbosity >= 1 {
+            println!("Wrote {}", out_file.display());
+        }
+    }
+
+    if clargs.verbosity >= 0 {
+        println!("Wrote the embedded {} ADCF, AICF, and windows tables to {}", release.name, out_dir);
+    }
+    return Ok(());
+}
+
+fn _diff_table_entries(embedded: &HashMap<String, Vec<(&'static str, String)>>, external: &HashMap<String, Vec<(&'static str, String)>>) -> Vec<String> {
+    let mut names: Vec<&String> = embedded.keys().chain(external.keys().filter(|k| !embedded.contains_key(*k))).collect();
+    names.sort_unstable();
+
+    // Entries only in one table note their state if it is not active, e.g. a commented-out window
+    let state = |fields: &Vec<(&'static str, String)>| match fields.first() {
+        Some(("state", s)) if s != "active" => format!(" ({})", s),
+        _ => String::new()
+    };
+
+    let mut lines = Vec::new();
+    for name in names {
+        match (embedded.get(name), external.get(name)) {
+            (Some(old), None) => lines.push(format!("- {}{}", name, state(old))),
+            (None, Some(new)) => lines.push(format!("+ {}{}", name, state(new))),
+            (Some(old), Some(new)) => {
+                let changes: Vec<String> = old.iter().zip(new.iter())
+                    .filter(|((_, o), (_, n))| o != n)
+                    .map(|((field, o), (_, n))| format!("{} {} -> {}", field, o, n))
+                    .collect();
+                if !changes.is_empty() {
+                    lines.push(format!("~ {}: {}", name, changes.join(", ")));
+                }
+            },
+            (None, None) => unreachable!()
+        }
     }
+    return lines;
+}
+
+fn diff_tables(table_kind: &str, table_file: &str, clargs: &CmdLineArgs) -> Result<bool, CheckError> {
+    let contents = match std::fs::read_to_string(table_file) {
+        Ok(s) => s,
+        Err(err) => return Err(CheckError::FileRead(String::from(table_file), err.to_string()))
+    };
+
+    // Each entry is reduced to its fields as strings, so that all three tables compare the same way
+    let release = clargs.release;
+    let (embedded, external) = match table_kind {
+        "adcf" => {
+            let fields = |t: HashMap<String, Adcf>| t.into_iter()
+                .map(|(k, a)| (k, vec![("adcf", a.adcf.to_string()), ("adcf_err", a.err.to_string()), ("g", a.g.to_string()), ("p", a.p.to_string())]))
+                .collect::<HashMap<_, _>>();
+            (fields(read_adcf_table(release)), fields(parse_adcf_table(&contents)?))
+        },
+        "aicf" => {
+            let fields = |t: HashMap<String, Aicf>| t.into_iter()
+                .map(|(k, a)| (k, vec![("aicf", a.aicf.to_string()), ("aicf_err", a.err.to_string())]))
+                .collect::<HashMap<_, _>>();
+            (fields(read_aicf_table(release)), fields(parse_aicf_table(&contents)?))
+        },
+        _ => {
+            // Commented-out windows only have a state, so a window that is commented out or back in
+            // shows as a change of state rather than of its other fields
+            let fields = |(t, skipped): (HashMap<String, Window>, Vec<String>)| t.into_iter()
+                .map(|(k, w)| (k, vec![("state", String::from("active")), ("sf", w.sf.to_string()),
+                                       ("params", format!("'{}'", w.params.join(" "))), ("gases", format!("'{}'", w.gases.join(" ")))]))
+                .chain(skipped.into_iter().map(|k| (k, vec![("state", String::from("commented out"))])))
+                .collect::<HashMap<_, _>>();
+            (fields(read_windows_table(release)), fields(parse_windows_table(&contents)?))
+        }
+    };
 
-    return aicfs;
+    let lines = _diff_table_entries(&embedded, &external);
+    if clargs.verbosity >= 0 {
+        for line in lines.iter() {
+            println!("{}", line);
+        }
+        let count = |prefix: &str| lines.iter().filter(|l| l.starts_with(prefix)).count();
+        println!("{} added, {} removed, {} changed in {} relative to the embedded {} {} table",
+                 count("+"), count("-"), count("~"), table_file, release.name, table_kind.to_uppercase());
+    }
+    return Ok(lines.is_empty());
 }
 
 
@@ -193,28 +508,49 @@ const WINDOWS_TABLE: &'static str = " Center   Width MIT A I F  Parameters_to_ f
 :5790.45   0.90  15 0 1 0  ncbf=2  fs  so     sf=1.000 : hcl h2o ch4";
 
 #[derive(Debug)]
-struct Window {
+#[allow(dead_code)]
+struct Window<'a> {
     center: i32,
-    gas: &'static str,
-    sf: f32
+    gas: &'a str,
+    sf: f32,
+    // The parameters_to_fit flags, e.g. ncbf=2, fs, sg, nv
+    params: Vec<&'a str>,
+    // All the gases fit in the window, starting with the main gas
+    gases: Vec<&'a str>
 }
 
 const EXPECTED_INGAAS_VARS: &'static str = "time,prior_time,cell_index,prior_altitude,ak_altitude,ak_slant_xgas_bin,ak_pressure,ak_slant_xco2_bin,ak_slant_xwco2_bin,ak_slant_xlco2_bin,ak_slant_xch4_bin,ak_slant_xhf_bin,ak_slant_xo2_bin,ak_slant_xn2o_bin,ak_slant_xco_bin,ak_slant_xh2o_bin,ak_xco2,ak_xwco2,ak_xlco2,ak_xch4,ak_xhf,ak_xo2,ak_xn2o,ak_xco,ak_xh2o,prior_index,prior_temperature,cell_temperature,prior_pressure,cell_pressure,prior_density,cell_density,prior_1h2o,cell_1h2o,prior_2h2o,cell_2h2o,prior_3h2o,cell_3h2o,prior_1co2,cell_1co2,prior_2co2,cell_2co2,prior_3co2,cell_3co2,prior_4co2,cell_4co2,prior_5co2,cell_5co2,prior_6co2,cell_6co2,prior_7co2,cell_7co2,prior_8co2,cell_8co2,prior_9co2,cell_9co2,prior_10co2,cell_10co2,prior_11co2,cell_11co2,prior_12co2,cell_12co2,prior_1o3,cell_1o3,prior_2o3,cell_2o3,prior_3o3,cell_3o3,prior_4o3,cell_4o3,prior_5o3,cell_5o3,prior_1n2o,cell_1n2o,prior_2n2o,cell_2n2o,prior_3n2o,cell_3n2o,prior_4n2o,cell_4n2o,prior_5n2o,cell_5n2o,prior_6n2o,cell_6n2o,prior_7n2o,cell_7n2o,prior_8n2o,cell_8n2o,prior_1co,cell_1co,prior_2co,cell_2co,prior_3co,cell_3co,prior_4co,cell_4co,prior_5co,cell_5co,prior_6co,cell_6co,prior_1ch4,cell_1ch4,prior_2ch4,cell_2ch4,prior_3ch4,cell_3ch4,prior_4ch4,cell_4ch4,prior_0o2,cell_0o2,prior_1o2,cell_1o2,prior_2o2,cell_2o2,prior_3o2,cell_3o2,prior_1no,cell_1no,prior_2no,cell_2no,prior_3no,cell_3no,prior_1so2,cell_1so2,prior_2so2,cell_2so2,prior_3so2,cell_3so2,prior_1no2,cell_1no2,prior_2no2,cell_2no2,prior_1nh3,cell_1nh3,prior_2nh3,cell_2nh3,prior_3nh3,cell_3nh3,prior_4nh3,cell_4nh3,prior_5nh3,cell_5nh3,prior_6nh3,cell_6nh3,prior_7nh3,cell_7nh3,prior_0hno3,cell_0hno3,prior_1hno3,cell_1hno3,prior_2hno3,cell_2hno3,prior_1oh,cell_1oh,prior_2oh,cell_2oh,prior_3oh,cell_3oh,prior_1hf,cell_1hf,prior_2hf,cell_2hf,prior_1hcl,cell_1hcl,prior_2hcl,cell_2hcl,prior_3hcl,cell_3hcl,prior_4hcl,cell_4hcl,prior_1hbr,cell_1hbr,prior_2hbr,cell_2hbr,prior_3hbr,cell_3hbr,prior_4hbr,cell_4hbr,prior_1hi,cell_1hi,prior_2hi,cell_2hi,prior_1clo,cell_1clo,prior_2clo,cell_2clo,prior_1ocs,cell_1ocs,prior_2ocs,cell_2ocs,prior_3ocs,cell_3ocs,prior_4ocs,cell_4ocs,prior_5ocs,cell_5ocs,prior_6ocs,cell_6ocs,prior_1h2co,cell_1h2co,prior_2h2co,cell_2h2co,prior_3h2co,cell_3h2co,prior_1hocl,cell_1hocl,prior_2hocl,cell_2hocl,prior_1ho2,cell_1ho2,prior_1h2o2,cell_1h2o2,prior_1hono,cell_1hono,prior_1ho2no2,cell_1ho2no2,prior_0n2o5,cell_0n2o5,prior_1n2o5,cell_1n2o5,prior_0clno3,cell_0clno3,prior_1clno3,cell_1clno3,prior_2clno3,cell_2clno3,prior_1hcn,cell_1hcn,prior_2hcn,cell_2hcn,prior_3hcn,cell_3hcn,prior_1ch3f,cell_1ch3f,prior_1ch3cl,cell_1ch3cl,prior_2ch3cl,cell_2ch3cl,prior_0cf4,cell_0cf4,prior_1cf4,cell_1cf4,prior_0ccl2f2,cell_0ccl2f2,prior_1ccl2f2,cell_1ccl2f2,prior_0ccl3f,cell_0ccl3f,prior_1ccl3f,cell_1ccl3f,prior_1ch3ccl3,cell_1ch3ccl3,prior_0ccl4,cell_0ccl4,prior_1ccl4,cell_1ccl4,prior_1cof2,cell_1cof2,prior_2cof2,cell_2cof2,prior_1cofcl,cell_1cofcl,prior_2cofcl,cell_2cofcl,prior_0c2h6,cell_0c2h6,prior_1c2h6,cell_1c2h6,prior_2c2h6,cell_2c2h6,prior_1c2h4,cell_1c2h4,prior_2c2h4,cell_2c2h4,prior_1c2h2,cell_1c2h2,prior_2c2h2,cell_2c2h2,prior_3c2h2,cell_3c2h2,prior_0n2,cell_0n2,prior_1n2,cell_1n2,prior_2n2,cell_2n2,prior_0chclf2,cell_0chclf2,prior_1chclf2,cell_1chclf2,prior_1cocl2,cell_1cocl2,prior_2cocl2,cell_2cocl2,prior_3cocl2,cell_3cocl2,prior_1ch3br,cell_1ch3br,prior_2ch3br,cell_2ch3br,prior_1ch3i,cell_1ch3i,prior_1hcooh,cell_1hcooh,prior_1h2s,cell_1h2s,prior_2h2s,cell_2h2s,prior_3h2s,cell_3h2s,prior_1chcl2f,cell_1chcl2f,prior_1hdo,cell_1hdo,prior_2hdo,cell_2hdo,prior_3hdo,cell_3hdo,prior_0sf6,cell_0sf6,prior_1sf6,cell_1sf6,prior_0f113,cell_0f113,prior_1f113,cell_1f113,prior_1clcn,cell_1clcn,prior_0f142b,cell_0f142b,prior_0dust_m,cell_0dust_m,prior_1ph3,cell_1ph3,prior_0ch3oh,cell_0ch3oh,prior_1ch3oh,cell_1ch3oh,prior_0ch3sh,cell_0ch3sh,prior_0ch3cho,cell_0ch3cho,prior_0ch3cn,cell_0ch3cn,prior_1ch3cn,cell_1ch3cn,prior_0pan,cell_0pan,prior_0nf3,cell_0nf3,prior_0cloocl,cell_0cloocl,prior_0clclo2,cell_0clclo2,prior_0cloclo,cell_0cloclo,prior_0chf3,cell_0chf3,prior_0f141b,cell_0f141b,prior_0ch3cooh,cell_0ch3cooh,prior_0cirrus6,cell_0cirrus6,prior_0cirrus15,cell_0cirrus15,prior_0c3h8,cell_0c3h8,prior_1c3h8,cell_1c3h8,prior_1d2o,cell_1d2o,prior_2d2o,cell_2d2o,prior_3d2o,cell_3d2o,prior_0sa_venus,cell_0sa_venus,prior_0c6h6,cell_0c6h6,prior_1c6h6,cell_1c6h6,prior_0c3h6,cell_0c3h6,prior_1c3h6,cell_1c3h6,prior_0ch3coch3,cell_0ch3coch3,prior_0cfh2cf3,cell_0cfh2cf3,prior_0n-c4h10,cell_0n-c4h10,prior_0c5h8,cell_0c5h8,prior_1luft,cell_1luft,prior_gravity,prior_equivalent_latitude,prior_tropopause_altitude,prior_modfile,prior_vmrfile,prior_effective_latitude,prior_mid_tropospheric_potential_temperature,config_checksum,apriori_checksum,runlog_checksum,levels_checksum,mav_checksum,ray_checksum,isotopologs_checksum,windows_checksum,telluric_linelists_checksum,solar_checksum,gfit_version,gsetup_version,flag,flagged_var_name,spectrum,year,day,hour,run,lat,long,zobs,zmin,solzen,azim,osds,opd,fovi,amal,graw,tins,pins,tout,pout,hout,sia,fvsi,wspd,wdir,tmod,pmod,h2o_dmf_out,h2o_dmf_mod,vsw_luft_6146,vsw_sf_luft_6146,vsw_ada_xluft_6146,vsw_luft_6146_error,vsw_ada_xluft_6146_error,vsw_hf_4038,vsw_sf_hf_4038,vsw_ada_xhf_4038,vsw_hf_4038_error,vsw_ada_xhf_4038_error,vsw_h2o_4565,vsw_sf_h2o_4565,vsw_ada_xh2o_4565,vsw_h2o_4565_error,vsw_ada_xh2o_4565_error,vsw_h2o_4570,vsw_sf_h2o_4570,vsw_ada_xh2o_4570,vsw_h2o_4570_error,vsw_ada_xh2o_4570_error,vsw_h2o_4571,vsw_sf_h2o_4571,vsw_ada_xh2o_4571,vsw_h2o_4571_error,vsw_ada_xh2o_4571_error,vsw_h2o_4576,vsw_sf_h2o_4576,vsw_ada_xh2o_4576,vsw_h2o_4576_error,vsw_ada_xh2o_4576_error,vsw_h2o_4598,vsw_sf_h2o_4598,vsw_ada_xh2o_4598,vsw_h2o_4598_error,vsw_ada_xh2o_4598_error,vsw_h2o_4611,vsw_sf_h2o_4611,vsw_ada_xh2o_4611,vsw_h2o_4611_error,vsw_ada_xh2o_4611_error,vsw_h2o_4622,vsw_sf_h2o_4622,vsw_ada_xh2o_4622,vsw_h2o_4622_error,vsw_ada_xh2o_4622_error,vsw_h2o_4631,vsw_sf_h2o_4631,vsw_ada_xh2o_4631,vsw_h2o_4631_error,vsw_ada_xh2o_4631_error,vsw_h2o_4699,vsw_sf_h2o_4699,vsw_ada_xh2o_4699,vsw_h2o_4699_error,vsw_ada_xh2o_4699_error,vsw_h2o_4734,vsw_sf_h2o_4734,vsw_ada_xh2o_4734,vsw_h2o_4734_error,vsw_ada_xh2o_4734_error,vsw_h2o_4761,vsw_sf_h2o_4761,vsw_ada_xh2o_4761,vsw_h2o_4761_error,vsw_ada_xh2o_4761_error,vsw_h2o_6076,vsw_sf_h2o_6076,vsw_ada_xh2o_6076,vsw_h2o_6076_error,vsw_ada_xh2o_6076_error,vsw_h2o_6099,vsw_sf_h2o_6099,vsw_ada_xh2o_6099,vsw_h2o_6099_error,vsw_ada_xh2o_6099_error,vsw_h2o_6125,vsw_sf_h2o_6125,vsw_ada_xh2o_6125,vsw_h2o_6125_error,vsw_ada_xh2o_6125_error,vsw_h2o_6177,vsw_sf_h2o_6177,vsw_ada_xh2o_6177,vsw_h2o_6177_error,vsw_ada_xh2o_6177_error,vsw_h2o_6255,vsw_sf_h2o_6255,vsw_ada_xh2o_6255,vsw_h2o_6255_error,vsw_ada_xh2o_6255_error,vsw_h2o_6301,vsw_sf_h2o_6301,vsw_ada_xh2o_6301,vsw_h2o_6301_error,vsw_ada_xh2o_6301_error,vsw_h2o_6392,vsw_sf_h2o_6392,vsw_ada_xh2o_6392,vsw_h2o_6392_error,vsw_ada_xh2o_6392_error,vsw_h2o_6401,vsw_sf_h2o_6401,vsw_ada_xh2o_6401,vsw_h2o_6401_error,vsw_ada_xh2o_6401_error,vsw_h2o_6469,vsw_sf_h2o_6469,vsw_ada_xh2o_6469,vsw_h2o_6469_error,vsw_ada_xh2o_6469_error,vsw_th2o_4054,vsw_sf_th2o_4054,vsw_ada_xth2o_4054,vsw_th2o_4054_error,vsw_ada_xth2o_4054_error,vsw_th2o_4255,vsw_sf_th2o_4255,vsw_ada_xth2o_4255,vsw_th2o_4255_error,vsw_ada_xth2o_4255_error,vsw_th2o_4325,vsw_sf_th2o_4325,vsw_ada_xth2o_4325,vsw_th2o_4325_error,vsw_ada_xth2o_4325_error,vsw_th2o_4493,vsw_sf_th2o_4493,vsw_ada_xth2o_4493,vsw_th2o_4493_error,vsw_ada_xth2o_4493_error,vsw_th2o_4516,vsw_sf_th2o_4516,vsw_ada_xth2o_4516,vsw_th2o_4516_error,vsw_ada_xth2o_4516_error,vsw_th2o_4524,vsw_sf_th2o_4524,vsw_ada_xth2o_4524,vsw_th2o_4524_error,vsw_ada_xth2o_4524_error,vsw_th2o_4633,vsw_sf_th2o_4633,vsw_ada_xth2o_4633,vsw_th2o_4633_error,vsw_ada_xth2o_4633_error,vsw_hdo_4054,vsw_sf_hdo_4054,vsw_ada_xhdo_4054,vsw_hdo_4054_error,vsw_ada_xhdo_4054_error,vsw_hdo_4067,vsw_sf_hdo_4067,vsw_ada_xhdo_4067,vsw_hdo_4067_error,vsw_ada_xhdo_4067_error,vsw_hdo_4116,vsw_sf_hdo_4116,vsw_ada_xhdo_4116,vsw_hdo_4116_error,vsw_ada_xhdo_4116_error,vsw_hdo_4212,vsw_sf_hdo_4212,vsw_ada_xhdo_4212,vsw_hdo_4212_error,vsw_ada_xhdo_4212_error,vsw_hdo_4232,vsw_sf_hdo_4232,vsw_ada_xhdo_4232,vsw_hdo_4232_error,vsw_ada_xhdo_4232_error,vsw_hdo_6330,vsw_sf_hdo_6330,vsw_ada_xhdo_6330,vsw_hdo_6330_error,vsw_ada_xhdo_6330_error,vsw_hdo_6377,vsw_sf_hdo_6377,vsw_ada_xhdo_6377,vsw_hdo_6377_error,vsw_ada_xhdo_6377_error,vsw_hdo_6458,vsw_sf_hdo_6458,vsw_ada_xhdo_6458,vsw_hdo_6458_error,vsw_ada_xhdo_6458_error,vsw_co_4290,vsw_sf_co_4290,vsw_ada_xco_4290,vsw_co_4290_error,vsw_ada_xco_4290_error,vsw_n2o_4395,vsw_sf_n2o_4395,vsw_ada_xn2o_4395,vsw_n2o_4395_error,vsw_ada_xn2o_4395_error,vsw_n2o_4430,vsw_sf_n2o_4430,vsw_ada_xn2o_4430,vsw_n2o_4430_error,vsw_ada_xn2o_4430_error,vsw_n2o_4719,vsw_sf_n2o_4719,vsw_ada_xn2o_4719,vsw_n2o_4719_error,vsw_ada_xn2o_4719_error,vsw_ch4_5938,vsw_sf_ch4_5938,vsw_ada_xch4_5938,vsw_ch4_5938_error,vsw_ada_xch4_5938_error,vsw_ch4_6002,vsw_sf_ch4_6002,vsw_ada_xch4_6002,vsw_ch4_6002_error,vsw_ada_xch4_6002_error,vsw_ch4_6076,vsw_sf_ch4_6076,vsw_ada_xch4_6076,vsw_ch4_6076_error,vsw_ada_xch4_6076_error,vsw_lco2_4852,vsw_sf_lco2_4852,vsw_ada_xlco2_4852,vsw_lco2_4852_error,vsw_ada_xlco2_4852_error,vsw_zco2_4852,vsw_sf_zco2_4852,vsw_ada_xzco2_4852,vsw_zco2_4852_error,vsw_ada_xzco2_4852_error,vsw_zco2_4852a,vsw_sf_zco2_4852a,vsw_ada_xzco2_4852a,vsw_zco2_4852a_error,vsw_ada_xzco2_4852a_error,vsw_fco2_6154,vsw_sf_fco2_6154,vsw_ada_xfco2_6154,vsw_fco2_6154_error,vsw_ada_xfco2_6154_error,vsw_wco2_6073,vsw_sf_wco2_6073,vsw_ada_xwco2_6073,vsw_wco2_6073_error,vsw_ada_xwco2_6073_error,vsw_co2_6220,vsw_sf_co2_6220,vsw_ada_xco2_6220,vsw_co2_6220_error,vsw_ada_xco2_6220_error,vsw_co2_6339,vsw_sf_co2_6339,vsw_ada_xco2_6339,vsw_co2_6339_error,vsw_ada_xco2_6339_error,vsw_o2_7885,vsw_sf_o2_7885,vsw_ada_xo2_7885,vsw_o2_7885_error,vsw_ada_xo2_7885_error,vsw_hcl_5625,vsw_sf_hcl_5625,vsw_ada_xhcl_5625,vsw_hcl_5625_error,vsw_ada_xhcl_5625_error,vsw_hcl_5687,vsw_sf_hcl_5687,vsw_ada_xhcl_5687,vsw_hcl_5687_error,vsw_ada_xhcl_5687_error,vsw_hcl_5702,vsw_sf_hcl_5702,vsw_ada_xhcl_5702,vsw_hcl_5702_error,vsw_ada_xhcl_5702_error,vsw_hcl_5735,vsw_sf_hcl_5735,vsw_ada_xhcl_5735,vsw_hcl_5735_error,vsw_ada_xhcl_5735_error,vsw_hcl_5739,vsw_sf_hcl_5739,vsw_ada_xhcl_5739,vsw_hcl_5739_error,vsw_ada_xhcl_5739_error,xluft,vsf_luft,column_luft,ada_xluft,xluft_error,vsf_luft_error,column_luft_error,ada_xluft_error,xhf,vsf_hf,column_hf,ada_xhf,xhf_error,vsf_hf_error,column_hf_error,ada_xhf_error,xh2o,vsf_h2o,column_h2o,ada_xh2o,xh2o_error,vsf_h2o_error,column_h2o_error,ada_xh2o_error,xth2o,vsf_th2o,column_th2o,ada_xth2o,xth2o_error,vsf_th2o_error,column_th2o_error,ada_xth2o_error,xhdo,vsf_hdo,column_hdo,ada_xhdo,xhdo_error,vsf_hdo_error,column_hdo_error,ada_xhdo_error,xco,vsf_co,column_co,ada_xco,xco_error,vsf_co_error,column_co_error,ada_xco_error,xn2o,vsf_n2o,column_n2o,ada_xn2o,xn2o_error,vsf_n2o_error,column_n2o_error,ada_xn2o_error,xch4,vsf_ch4,column_ch4,ada_xch4,xch4_error,vsf_ch4_error,column_ch4_error,ada_xch4_error,xlco2,vsf_lco2,column_lco2,ada_xlco2,xlco2_error,vsf_lco2_error,column_lco2_error,ada_xlco2_error,xzco2,vsf_zco2,column_zco2,ada_xzco2,xzco2_error,vsf_zco2_error,column_zco2_error,ada_xzco2_error,xfco2,vsf_fco2,column_fco2,ada_xfco2,xfco2_error,vsf_fco2_error,column_fco2_error,ada_xfco2_error,xwco2,vsf_wco2,column_wco2,ada_xwco2,xwco2_error,vsf_wco2_error,column_wco2_error,ada_xwco2_error,xco2,vsf_co2,column_co2,ada_xco2,xco2_error,vsf_co2_error,column_co2_error,ada_xco2_error,xo2,vsf_o2,column_o2,ada_xo2,xo2_error,vsf_o2_error,column_o2_error,ada_xo2_error,xhcl,vsf_hcl,column_hcl,ada_xhcl,xhcl_error,vsf_hcl_error,column_hcl_error,ada_xhcl_error,lst,lse,lsu,lsf,dip,mvd,xco2_6220_adcf,xco2_6220_adcf_error,xco2_6220_g,xco2_6220_p,xco2_6339_adcf,xco2_6339_adcf_error,xco2_6339_g,xco2_6339_p,xlco2_4852_adcf,xlco2_4852_adcf_error,xlco2_4852_g,xlco2_4852_p,xwco2_6073_adcf,xwco2_6073_adcf_error,xwco2_6073_g,xwco2_6073_p,xwco2_6500_adcf,xwco2_6500_adcf_error,xwco2_6500_g,xwco2_6500_p,xch4_5938_adcf,xch4_5938_adcf_error,xch4_5938_g,xch4_5938_p,xch4_6002_adcf,xch4_6002_adcf_error,xch4_6002_g,xch4_6002_p,xch4_6076_adcf,xch4_6076_adcf_error,xch4_6076_g,xch4_6076_p,xn2o_4395_adcf,xn2o_4395_adcf_error,xn2o_4395_g,xn2o_4395_p,xn2o_4430_adcf,xn2o_4430_adcf_error,xn2o_4430_g,xn2o_4430_p,xn2o_4719_adcf,xn2o_4719_adcf_error,xn2o_4719_g,xn2o_4719_p,xco_4233_adcf,xco_4233_adcf_error,xco_4233_g,xco_4233_p,xco_4290_adcf,xco_4290_adcf_error,xco_4290_g,xco_4290_p,xluft_6146_adcf,xluft_6146_adcf_error,xluft_6146_g,xluft_6146_p,xco2_aicf,xco2_aicf_error,aicf_xco2_scale,xwco2_aicf,xwco2_aicf_error,aicf_xwco2_scale,xlco2_aicf,xlco2_aicf_error,aicf_xlco2_scale,xch4_aicf,xch4_aicf_error,aicf_xch4_scale,xn2o_aicf,xn2o_aicf_error,aicf_xn2o_scale,xco_aicf,xco_aicf_error,aicf_xco_scale,xh2o_aicf,xh2o_aicf_error,aicf_xh2o_scale,xluft_aicf,xluft_aicf_error,aicf_xluft_scale,hf_4038_nit,hf_4038_cl,hf_4038_ct,hf_4038_cc,hf_4038_fs,hf_4038_sg,hf_4038_zo,hf_4038_rmsocl,hf_4038_zpres,hf_4038_am_hf,hf_4038_ovc_hf,hf_4038_vsf_hf,hf_4038_vsf_hf_error,hf_4038_am_h2o,hf_4038_ovc_h2o,hf_4038_vsf_h2o,hf_4038_vsf_h2o_error,hf_4038_ncbf,hf_4038_cfampocl,hf_4038_cfperiod,hf_4038_cfphase,hf_4038_cbf_01,hf_4038_cbf_02,h2o_4565_nit,h2o_4565_cl,h2o_4565_ct,h2o_4565_cc,h2o_4565_fs,h2o_4565_sg,h2o_4565_zo,h2o_4565_rmsocl,h2o_4565_zpres,h2o_4565_am_h2o,h2o_4565_ovc_h2o,h2o_4565_vsf_h2o,h2o_4565_vsf_h2o_error,h2o_4565_am_co2,h2o_4565_ovc_co2,h2o_4565_vsf_co2,h2o_4565_vsf_co2_error,h2o_4565_am_ch4,h2o_4565_ovc_ch4,h2o_4565_vsf_ch4,h2o_4565_vsf_ch4_error,h2o_4565_ncbf,h2o_4565_cfampocl,h2o_4565_cfperiod,h2o_4565_cfphase,h2o_4565_cbf_01,h2o_4565_cbf_02,h2o_4570_nit,h2o_4570_cl,h2o_4570_ct,h2o_4570_cc,h2o_4570_fs,h2o_4570_sg,h2o_4570_zo,h2o_4570_rmsocl,h2o_4570_zpres,h2o_4570_am_h2o,h2o_4570_ovc_h2o,h2o_4570_vsf_h2o,h2o_4570_vsf_h2o_error,h2o_4570_am_co2,h2o_4570_ovc_co2,h2o_4570_vsf_co2,h2o_4570_vsf_co2_error,h2o_4570_am_ch4,h2o_4570_ovc_ch4,h2o_4570_vsf_ch4,h2o_4570_vsf_ch4_error,h2o_4570_ncbf,h2o_4570_cfampocl,h2o_4570_cfperiod,h2o_4570_cfphase,h2o_4570_cbf_01,h2o_4570_cbf_02,h2o_4571_nit,h2o_4571_cl,h2o_4571_ct,h2o_4571_cc,h2o_4571_fs,h2o_4571_sg,h2o_4571_zo,h2o_4571_rmsocl,h2o_4571_zpres,h2o_4571_am_h2o,h2o_4571_ovc_h2o,h2o_4571_vsf_h2o,h2o_4571_vsf_h2o_error,h2o_4571_am_co2,h2o_4571_ovc_co2,h2o_4571_vsf_co2,h2o_4571_vsf_co2_error,h2o_4571_am_ch4,h2o_4571_ovc_ch4,h2o_4571_vsf_ch4,h2o_4571_vsf_ch4_error,h2o_4571_ncbf,h2o_4571_cfampocl,h2o_4571_cfperiod,h2o_4571_cfphase,h2o_4571_cbf_01,h2o_4571_cbf_02,h2o_4576_nit,h2o_4576_cl,h2o_4576_ct,h2o_4576_cc,h2o_4576_fs,h2o_4576_sg,h2o_4576_zo,h2o_4576_rmsocl,h2o_4576_zpres,h2o_4576_am_h2o,h2o_4576_ovc_h2o,h2o_4576_vsf_h2o,h2o_4576_vsf_h2o_error,h2o_4576_am_ch4,h2o_4576_ovc_ch4,h2o_4576_vsf_ch4,h2o_4576_vsf_ch4_error,h2o_4576_ncbf,h2o_4576_cfampocl,h2o_4576_cfperiod,h2o_4576_cfphase,h2o_4576_cbf_01,h2o_4576_cbf_02,h2o_4598_nit,h2o_4598_cl,h2o_4598_ct,h2o_4598_cc,h2o_4598_fs,h2o_4598_sg,h2o_4598_zo,h2o_4598_rmsocl,h2o_4598_zpres,h2o_4598_am_h2o,h2o_4598_ovc_h2o,h2o_4598_vsf_h2o,h2o_4598_vsf_h2o_error,h2o_4598_am_ch4,h2o_4598_ovc_ch4,h2o_4598_vsf_ch4,h2o_4598_vsf_ch4_error,h2o_4598_am_co2,h2o_4598_ovc_co2,h2o_4598_vsf_co2,h2o_4598_vsf_co2_error,h2o_4598_am_n2o,h2o_4598_ovc_n2o,h2o_4598_vsf_n2o,h2o_4598_vsf_n2o_error,h2o_4598_ncbf,h2o_4598_cfampocl,h2o_4598_cfperiod,h2o_4598_cfphase,h2o_4598_cbf_01,h2o_4598_cbf_02,h2o_4611_nit,h2o_4611_cl,h2o_4611_ct,h2o_4611_cc,h2o_4611_fs,h2o_4611_sg,h2o_4611_zo,h2o_4611_rmsocl,h2o_4611_zpres,h2o_4611_am_h2o,h2o_4611_ovc_h2o,h2o_4611_vsf_h2o,h2o_4611_vsf_h2o_error,h2o_4611_am_ch4,h2o_4611_ovc_ch4,h2o_4611_vsf_ch4,h2o_4611_vsf_ch4_error,h2o_4611_am_co2,h2o_4611_ovc_co2,h2o_4611_vsf_co2,h2o_4611_vsf_co2_error,h2o_4611_am_n2o,h2o_4611_ovc_n2o,h2o_4611_vsf_n2o,h2o_4611_vsf_n2o_error,h2o_4611_ncbf,h2o_4611_cfampocl,h2o_4611_cfperiod,h2o_4611_cfphase,h2o_4611_cbf_01,h2o_4611_cbf_02,h2o_4622_nit,h2o_4622_cl,h2o_4622_ct,h2o_4622_cc,h2o_4622_fs,h2o_4622_sg,h2o_4622_zo,h2o_4622_rmsocl,h2o_4622_zpres,h2o_4622_am_h2o,h2o_4622_ovc_h2o,h2o_4622_vsf_h2o,h2o_4622_vsf_h2o_error,h2o_4622_am_co2,h2o_4622_ovc_co2,h2o_4622_vsf_co2,h2o_4622_vsf_co2_error,h2o_4622_am_n2o,h2o_4622_ovc_n2o,h2o_4622_vsf_n2o,h2o_4622_vsf_n2o_error,h2o_4622_ncbf,h2o_4622_cfampocl,h2o_4622_cfperiod,h2o_4622_cfphase,h2o_4622_cbf_01,h2o_4622_cbf_02,h2o_4631_nit,h2o_4631_cl,h2o_4631_ct,h2o_4631_cc,h2o_4631_fs,h2o_4631_sg,h2o_4631_zo,h2o_4631_rmsocl,h2o_4631_zpres,h2o_4631_am_h2o,h2o_4631_ovc_h2o,h2o_4631_vsf_h2o,h2o_4631_vsf_h2o_error,h2o_4631_ncbf,h2o_4631_cfampocl,h2o_4631_cfperiod,h2o_4631_cfphase,h2o_4631_cbf_01,h2o_4631_cbf_02,h2o_4699_nit,h2o_4699_cl,h2o_4699_ct,h2o_4699_cc,h2o_4699_fs,h2o_4699_sg,h2o_4699_zo,h2o_4699_rmsocl,h2o_4699_zpres,h2o_4699_am_h2o,h2o_4699_ovc_h2o,h2o_4699_vsf_h2o,h2o_4699_vsf_h2o_error,h2o_4699_am_co2,h2o_4699_ovc_co2,h2o_4699_vsf_co2,h2o_4699_vsf_co2_error,h2o_4699_am_n2o,h2o_4699_ovc_n2o,h2o_4699_vsf_n2o,h2o_4699_vsf_n2o_error,h2o_4699_ncbf,h2o_4699_cfampocl,h2o_4699_cfperiod,h2o_4699_cfphase,h2o_4699_cbf_01,h2o_4699_cbf_02,h2o_4734_nit,h2o_4734_cl,h2o_4734_ct,h2o_4734_cc,h2o_4734_fs,h2o_4734_sg,h2o_4734_zo,h2o_4734_rmsocl,h2o_4734_zpres,h2o_4734_am_h2o,h2o_4734_ovc_h2o,h2o_4734_vsf_h2o,h2o_4734_vsf_h2o_error,h2o_4734_am_co2,h2o_4734_ovc_co2,h2o_4734_vsf_co2,h2o_4734_vsf_co2_error,h2o_4734_am_n2o,h2o_4734_ovc_n2o,h2o_4734_vsf_n2o,h2o_4734_vsf_n2o_error,h2o_4734_ncbf,h2o_4734_cfampocl,h2o_4734_cfperiod,h2o_4734_cfphase,h2o_4734_cbf_01,h2o_4734_cbf_02,h2o_4761_nit,h2o_4761_cl,h2o_4761_ct,h2o_4761_cc,h2o_4761_fs,h2o_4761_sg,h2o_4761_zo,h2o_4761_rmsocl,h2o_4761_zpres,h2o_4761_am_h2o,h2o_4761_ovc_h2o,h2o_4761_vsf_h2o,h2o_4761_vsf_h2o_error,h2o_4761_am_co2,h2o_4761_ovc_co2,h2o_4761_vsf_co2,h2o_4761_vsf_co2_error,h2o_4761_ncbf,h2o_4761_cfampocl,h2o_4761_cfperiod,h2o_4761_cfphase,h2o_4761_cbf_01,h2o_4761_cbf_02,h2o_6076_nit,h2o_6076_cl,h2o_6076_ct,h2o_6076_cc,h2o_6076_fs,h2o_6076_sg,h2o_6076_zo,h2o_6076_rmsocl,h2o_6076_zpres,h2o_6076_am_h2o,h2o_6076_ovc_h2o,h2o_6076_vsf_h2o,h2o_6076_vsf_h2o_error,h2o_6076_am_ch4,h2o_6076_ovc_ch4,h2o_6076_vsf_ch4,h2o_6076_vsf_ch4_error,h2o_6076_am_hdo,h2o_6076_ovc_hdo,h2o_6076_vsf_hdo,h2o_6076_vsf_hdo_error,h2o_6076_am_co2,h2o_6076_ovc_co2,h2o_6076_vsf_co2,h2o_6076_vsf_co2_error,h2o_6076_ncbf,h2o_6076_cfampocl,h2o_6076_cfperiod,h2o_6076_cfphase,h2o_6076_cbf_01,h2o_6076_cbf_02,h2o_6099_nit,h2o_6099_cl,h2o_6099_ct,h2o_6099_cc,h2o_6099_fs,h2o_6099_sg,h2o_6099_zo,h2o_6099_rmsocl,h2o_6099_zpres,h2o_6099_am_h2o,h2o_6099_ovc_h2o,h2o_6099_vsf_h2o,h2o_6099_vsf_h2o_error,h2o_6099_am_co2,h2o_6099_ovc_co2,h2o_6099_vsf_co2,h2o_6099_vsf_co2_error,h2o_6099_ncbf,h2o_6099_cfampocl,h2o_6099_cfperiod,h2o_6099_cfphase,h2o_6099_cbf_01,h2o_6099_cbf_02,h2o_6125_nit,h2o_6125_cl,h2o_6125_ct,h2o_6125_cc,h2o_6125_fs,h2o_6125_sg,h2o_6125_zo,h2o_6125_rmsocl,h2o_6125_zpres,h2o_6125_am_h2o,h2o_6125_ovc_h2o,h2o_6125_vsf_h2o,h2o_6125_vsf_h2o_error,h2o_6125_am_hdo,h2o_6125_ovc_hdo,h2o_6125_vsf_hdo,h2o_6125_vsf_hdo_error,h2o_6125_am_co2,h2o_6125_ovc_co2,h2o_6125_vsf_co2,h2o_6125_vsf_co2_error,h2o_6125_am_ch4,h2o_6125_ovc_ch4,h2o_6125_vsf_ch4,h2o_6125_vsf_ch4_error,h2o_6125_ncbf,h2o_6125_cfampocl,h2o_6125_cfperiod,h2o_6125_cfphase,h2o_6125_cbf_01,h2o_6125_cbf_02,h2o_6177_nit,h2o_6177_cl,h2o_6177_ct,h2o_6177_cc,h2o_6177_fs,h2o_6177_sg,h2o_6177_zo,h2o_6177_rmsocl,h2o_6177_zpres,h2o_6177_am_h2o,h2o_6177_ovc_h2o,h2o_6177_vsf_h2o,h2o_6177_vsf_h2o_error,h2o_6177_am_hdo,h2o_6177_ovc_hdo,h2o_6177_vsf_hdo,h2o_6177_vsf_hdo_error,h2o_6177_am_co2,h2o_6177_ovc_co2,h2o_6177_vsf_co2,h2o_6177_vsf_co2_error,h2o_6177_am_ch4,h2o_6177_ovc_ch4,h2o_6177_vsf_ch4,h2o_6177_vsf_ch4_error,h2o_6177_ncbf,h2o_6177_cfampocl,h2o_6177_cfperiod,h2o_6177_cfphase,h2o_6177_cbf_01,h2o_6177_cbf_02,h2o_6255_nit,h2o_6255_cl,h2o_6255_ct,h2o_6255_cc,h2o_6255_fs,h2o_6255_sg,h2o_6255_zo,h2o_6255_rmsocl,h2o_6255_zpres,h2o_6255_am_h2o,h2o_6255_ovc_h2o,h2o_6255_vsf_h2o,h2o_6255_vsf_h2o_error,h2o_6255_am_co2,h2o_6255_ovc_co2,h2o_6255_vsf_co2,h2o_6255_vsf_co2_error,h2o_6255_am_hdo,h2o_6255_ovc_hdo,h2o_6255_vsf_hdo,h2o_6255_vsf_hdo_error,h2o_6255_ncbf,h2o_6255_cfampocl,h2o_6255_cfperiod,h2o_6255_cfphase,h2o_6255_cbf_01,h2o_6255_cbf_02,h2o_6301_nit,h2o_6301_cl,h2o_6301_ct,h2o_6301_cc,h2o_6301_fs,h2o_6301_sg,h2o_6301_zo,h2o_6301_rmsocl,h2o_6301_zpres,h2o_6301_am_h2o,h2o_6301_ovc_h2o,h2o_6301_vsf_h2o,h2o_6301_vsf_h2o_error,h2o_6301_am_co2,h2o_6301_ovc_co2,h2o_6301_vsf_co2,h2o_6301_vsf_co2_error,h2o_6301_am_hdo,h2o_6301_ovc_hdo,h2o_6301_vsf_hdo,h2o_6301_vsf_hdo_error,h2o_6301_ncbf,h2o_6301_cfampocl,h2o_6301_cfperiod,h2o_6301_cfphase,h2o_6301_cbf_01,h2o_6301_cbf_02,h2o_6392_nit,h2o_6392_cl,h2o_6392_ct,h2o_6392_cc,h2o_6392_fs,h2o_6392_sg,h2o_6392_zo,h2o_6392_rmsocl,h2o_6392_zpres,h2o_6392_am_h2o,h2o_6392_ovc_h2o,h2o_6392_vsf_h2o,h2o_6392_vsf_h2o_error,h2o_6392_am_hdo,h2o_6392_ovc_hdo,h2o_6392_vsf_hdo,h2o_6392_vsf_hdo_error,h2o_6392_ncbf,h2o_6392_cfampocl,h2o_6392_cfperiod,h2o_6392_cfphase,h2o_6392_cbf_01,h2o_6392_cbf_02,h2o_6401_nit,h2o_6401_cl,h2o_6401_ct,h2o_6401_cc,h2o_6401_fs,h2o_6401_sg,h2o_6401_zo,h2o_6401_rmsocl,h2o_6401_zpres,h2o_6401_am_h2o,h2o_6401_ovc_h2o,h2o_6401_vsf_h2o,h2o_6401_vsf_h2o_error,h2o_6401_am_hdo,h2o_6401_ovc_hdo,h2o_6401_vsf_hdo,h2o_6401_vsf_hdo_error,h2o_6401_am_co2,h2o_6401_ovc_co2,h2o_6401_vsf_co2,h2o_6401_vsf_co2_error,h2o_6401_ncbf,h2o_6401_cfampocl,h2o_6401_cfperiod,h2o_6401_cfphase,h2o_6401_cbf_01,h2o_6401_cbf_02,h2o_6469_nit,h2o_6469_cl,h2o_6469_ct,h2o_6469_cc,h2o_6469_fs,h2o_6469_sg,h2o_6469_zo,h2o_6469_rmsocl,h2o_6469_zpres,h2o_6469_am_h2o,h2o_6469_ovc_h2o,h2o_6469_vsf_h2o,h2o_6469_vsf_h2o_error,h2o_6469_am_co2,h2o_6469_ovc_co2,h2o_6469_vsf_co2,h2o_6469_vsf_co2_error,h2o_6469_am_hdo,h2o_6469_ovc_hdo,h2o_6469_vsf_hdo,h2o_6469_vsf_hdo_error,h2o_6469_ncbf,h2o_6469_cfampocl,h2o_6469_cfperiod,h2o_6469_cfphase,h2o_6469_cbf_01,h2o_6469_cbf_02,th2o_4054_nit,th2o_4054_cl,th2o_4054_ct,th2o_4054_cc,th2o_4054_fs,th2o_4054_sg,th2o_4054_zo,th2o_4054_rmsocl,th2o_4054_zpres,th2o_4054_am_th2o,th2o_4054_ovc_th2o,th2o_4054_vsf_th2o,th2o_4054_vsf_th2o_error,th2o_4054_am_ch4,th2o_4054_ovc_ch4,th2o_4054_vsf_ch4,th2o_4054_vsf_ch4_error,th2o_4054_am_n2o,th2o_4054_ovc_n2o,th2o_4054_vsf_n2o,th2o_4054_vsf_n2o_error,th2o_4054_am_hdo,th2o_4054_ovc_hdo,th2o_4054_vsf_hdo,th2o_4054_vsf_hdo_error,th2o_4054_ncbf,th2o_4054_cfampocl,th2o_4054_cfperiod,th2o_4054_cfphase,th2o_4054_cbf_01,th2o_4054_cbf_02,th2o_4255_nit,th2o_4255_cl,th2o_4255_ct,th2o_4255_cc,th2o_4255_fs,th2o_4255_sg,th2o_4255_zo,th2o_4255_rmsocl,th2o_4255_zpres,th2o_4255_am_th2o,th2o_4255_ovc_th2o,th2o_4255_vsf_th2o,th2o_4255_vsf_th2o_error,th2o_4255_am_ch4,th2o_4255_ovc_ch4,th2o_4255_vsf_ch4,th2o_4255_vsf_ch4_error,th2o_4255_am_co,th2o_4255_ovc_co,th2o_4255_vsf_co,th2o_4255_vsf_co_error,th2o_4255_am_hdo,th2o_4255_ovc_hdo,th2o_4255_vsf_hdo,th2o_4255_vsf_hdo_error,th2o_4255_ncbf,th2o_4255_cfampocl,th2o_4255_cfperiod,th2o_4255_cfphase,th2o_4255_cbf_01,th2o_4255_cbf_02,th2o_4325_nit,th2o_4325_cl,th2o_4325_ct,th2o_4325_cc,th2o_4325_fs,th2o_4325_sg,th2o_4325_zo,th2o_4325_rmsocl,th2o_4325_zpres,th2o_4325_am_th2o,th2o_4325_ovc_th2o,th2o_4325_vsf_th2o,th2o_4325_vsf_th2o_error,th2o_4325_am_ch4,th2o_4325_ovc_ch4,th2o_4325_vsf_ch4,th2o_4325_vsf_ch4_error,th2o_4325_am_co,th2o_4325_ovc_co,th2o_4325_vsf_co,th2o_4325_vsf_co_error,th2o_4325_am_hdo,th2o_4325_ovc_hdo,th2o_4325_vsf_hdo,th2o_4325_vsf_hdo_error,th2o_4325_ncbf,th2o_4325_cfampocl,th2o_4325_cfperiod,th2o_4325_cfphase,th2o_4325_cbf_01,th2o_4325_cbf_02,th2o_4493_nit,th2o_4493_cl,th2o_4493_ct,th2o_4493_cc,th2o_4493_fs,th2o_4493_sg,th2o_4493_zo,th2o_4493_rmsocl,th2o_4493_zpres,th2o_4493_am_th2o,th2o_4493_ovc_th2o,th2o_4493_vsf_th2o,th2o_4493_vsf_th2o_error,th2o_4493_am_ch4,th2o_4493_ovc_ch4,th2o_4493_vsf_ch4,th2o_4493_vsf_ch4_error,th2o_4493_ncbf,th2o_4493_cfampocl,th2o_4493_cfperiod,th2o_4493_cfphase,th2o_4493_cbf_01,th2o_4493_cbf_02,th2o_4516_nit,th2o_4516_cl,th2o_4516_ct,th2o_4516_cc,th2o_4516_fs,th2o_4516_sg,th2o_4516_zo,th2o_4516_rmsocl,th2o_4516_zpres,th2o_4516_am_th2o,th2o_4516_ovc_th2o,th2o_4516_vsf_th2o,th2o_4516_vsf_th2o_error,th2o_4516_am_ch4,th2o_4516_ovc_ch4,th2o_4516_vsf_ch4,th2o_4516_vsf_ch4_error,th2o_4516_ncbf,th2o_4516_cfampocl,th2o_4516_cfperiod,th2o_4516_cfphase,th2o_4516_cbf_01,th2o_4516_cbf_02,th2o_4524_nit,th2o_4524_cl,th2o_4524_ct,th2o_4524_cc,th2o_4524_fs,th2o_4524_sg,th2o_4524_zo,th2o_4524_rmsocl,th2o_4524_zpres,th2o_4524_am_th2o,th2o_4524_ovc_th2o,th2o_4524_vsf_th2o,th2o_4524_vsf_th2o_error,th2o_4524_am_ch4,th2o_4524_ovc_ch4,th2o_4524_vsf_ch4,th2o_4524_vsf_ch4_error,th2o_4524_am_co2,th2o_4524_ovc_co2,th2o_4524_vsf_co2,th2o_4524_vsf_co2_error,th2o_4524_ncbf,th2o_4524_cfampocl,th2o_4524_cfperiod,th2o_4524_cfphase,th2o_4524_cbf_01,th2o_4524_cbf_02,th2o_4633_nit,th2o_4633_cl,th2o_4633_ct,th2o_4633_cc,th2o_4633_fs,th2o_4633_sg,th2o_4633_zo,th2o_4633_rmsocl,th2o_4633_zpres,th2o_4633_am_th2o,th2o_4633_ovc_th2o,th2o_4633_vsf_th2o,th2o_4633_vsf_th2o_error,th2o_4633_am_co2,th2o_4633_ovc_co2,th2o_4633_vsf_co2,th2o_4633_vsf_co2_error,th2o_4633_am_n2o,th2o_4633_ovc_n2o,th2o_4633_vsf_n2o,th2o_4633_vsf_n2o_error,th2o_4633_ncbf,th2o_4633_cfampocl,th2o_4633_cfperiod,th2o_4633_cfphase,th2o_4633_cbf_01,th2o_4633_cbf_02,hdo_4054_nit,hdo_4054_cl,hdo_4054_ct,hdo_4054_cc,hdo_4054_fs,hdo_4054_sg,hdo_4054_zo,hdo_4054_rmsocl,hdo_4054_zpres,hdo_4054_am_hdo,hdo_4054_ovc_hdo,hdo_4054_vsf_hdo,hdo_4054_vsf_hdo_error,hdo_4054_am_h2o,hdo_4054_ovc_h2o,hdo_4054_vsf_h2o,hdo_4054_vsf_h2o_error,hdo_4054_am_ch4,hdo_4054_ovc_ch4,hdo_4054_vsf_ch4,hdo_4054_vsf_ch4_error,hdo_4054_ncbf,hdo_4054_cfampocl,hdo_4054_cfperiod,hdo_4054_cfphase,hdo_4054_cbf_01,hdo_4054_cbf_02,hdo_4067_nit,hdo_4067_cl,hdo_4067_ct,hdo_4067_cc,hdo_4067_fs,hdo_4067_sg,hdo_4067_zo,hdo_4067_rmsocl,hdo_4067_zpres,hdo_4067_am_hdo,hdo_4067_ovc_hdo,hdo_4067_vsf_hdo,hdo_4067_vsf_hdo_error,hdo_4067_am_h2o,hdo_4067_ovc_h2o,hdo_4067_vsf_h2o,hdo_4067_vsf_h2o_error,hdo_4067_am_ch4,hdo_4067_ovc_ch4,hdo_4067_vsf_ch4,hdo_4067_vsf_ch4_error,hdo_4067_ncbf,hdo_4067_cfampocl,hdo_4067_cfperiod,hdo_4067_cfphase,hdo_4067_cbf_01,hdo_4067_cbf_02,hdo_4116_nit,hdo_4116_cl,hdo_4116_ct,hdo_4116_cc,hdo_4116_fs,hdo_4116_sg,hdo_4116_zo,hdo_4116_rmsocl,hdo_4116_zpres,hdo_4116_am_hdo,hdo_4116_ovc_hdo,hdo_4116_vsf_hdo,hdo_4116_vsf_hdo_error,hdo_4116_am_h2o,hdo_4116_ovc_h2o,hdo_4116_vsf_h2o,hdo_4116_vsf_h2o_error,hdo_4116_am_ch4,hdo_4116_ovc_ch4,hdo_4116_vsf_ch4,hdo_4116_vsf_ch4_error,hdo_4116_ncbf,hdo_4116_cfampocl,hdo_4116_cfperiod,hdo_4116_cfphase,hdo_4116_cbf_01,hdo_4116_cbf_02,hdo_4212_nit,hdo_4212_cl,hdo_4212_ct,hdo_4212_cc,hdo_4212_fs,hdo_4212_sg,hdo_4212_zo,hdo_4212_rmsocl,hdo_4212_zpres,hdo_4212_am_hdo,hdo_4212_ovc_hdo,hdo_4212_vsf_hdo,hdo_4212_vsf_hdo_error,hdo_4212_am_h2o,hdo_4212_ovc_h2o,hdo_4212_vsf_h2o,hdo_4212_vsf_h2o_error,hdo_4212_am_ch4,hdo_4212_ovc_ch4,hdo_4212_vsf_ch4,hdo_4212_vsf_ch4_error,hdo_4212_ncbf,hdo_4212_cfampocl,hdo_4212_cfperiod,hdo_4212_cfphase,hdo_4212_cbf_01,hdo_4212_cbf_02,hdo_4232_nit,hdo_4232_cl,hdo_4232_ct,hdo_4232_cc,hdo_4232_fs,hdo_4232_sg,hdo_4232_zo,hdo_4232_rmsocl,hdo_4232_zpres,hdo_4232_am_hdo,hdo_4232_ovc_hdo,hdo_4232_vsf_hdo,hdo_4232_vsf_hdo_error,hdo_4232_am_h2o,hdo_4232_ovc_h2o,hdo_4232_vsf_h2o,hdo_4232_vsf_h2o_error,hdo_4232_am_ch4,hdo_4232_ovc_ch4,hdo_4232_vsf_ch4,hdo_4232_vsf_ch4_error,hdo_4232_am_co,hdo_4232_ovc_co,hdo_4232_vsf_co,hdo_4232_vsf_co_error,hdo_4232_ncbf,hdo_4232_cfampocl,hdo_4232_cfperiod,hdo_4232_cfphase,hdo_4232_cbf_01,hdo_4232_cbf_02,hdo_6330_nit,hdo_6330_cl,hdo_6330_ct,hdo_6330_cc,hdo_6330_fs,hdo_6330_sg,hdo_6330_zo,hdo_6330_rmsocl,hdo_6330_zpres,hdo_6330_am_hdo,hdo_6330_ovc_hdo,hdo_6330_vsf_hdo,hdo_6330_vsf_hdo_error,hdo_6330_am_h2o,hdo_6330_ovc_h2o,hdo_6330_vsf_h2o,hdo_6330_vsf_h2o_error,hdo_6330_am_co2,hdo_6330_ovc_co2,hdo_6330_vsf_co2,hdo_6330_vsf_co2_error,hdo_6330_ncbf,hdo_6330_cfampocl,hdo_6330_cfperiod,hdo_6330_cfphase,hdo_6330_cbf_01,hdo_6330_cbf_02,hdo_6330_cbf_03,hdo_6330_cbf_04,hdo_6377_nit,hdo_6377_cl,hdo_6377_ct,hdo_6377_cc,hdo_6377_fs,hdo_6377_sg,hdo_6377_zo,hdo_6377_rmsocl,hdo_6377_zpres,hdo_6377_am_hdo,hdo_6377_ovc_hdo,hdo_6377_vsf_hdo,hdo_6377_vsf_hdo_error,hdo_6377_am_h2o,hdo_6377_ovc_h2o,hdo_6377_vsf_h2o,hdo_6377_vsf_h2o_error,hdo_6377_am_co2,hdo_6377_ovc_co2,hdo_6377_vsf_co2,hdo_6377_vsf_co2_error,hdo_6377_ncbf,hdo_6377_cfampocl,hdo_6377_cfperiod,hdo_6377_cfphase,hdo_6377_cbf_01,hdo_6377_cbf_02,hdo_6377_cbf_03,hdo_6377_cbf_04,hdo_6458_nit,hdo_6458_cl,hdo_6458_ct,hdo_6458_cc,hdo_6458_fs,hdo_6458_sg,hdo_6458_zo,hdo_6458_rmsocl,hdo_6458_zpres,hdo_6458_am_hdo,hdo_6458_ovc_hdo,hdo_6458_vsf_hdo,hdo_6458_vsf_hdo_error,hdo_6458_am_h2o,hdo_6458_ovc_h2o,hdo_6458_vsf_h2o,hdo_6458_vsf_h2o_error,hdo_6458_am_co2,hdo_6458_ovc_co2,hdo_6458_vsf_co2,hdo_6458_vsf_co2_error,hdo_6458_ncbf,hdo_6458_cfampocl,hdo_6458_cfperiod,hdo_6458_cfphase,hdo_6458_cbf_01,hdo_6458_cbf_02,hdo_6458_cbf_03,hdo_6458_cbf_04,co_4290_nit,co_4290_cl,co_4290_ct,co_4290_cc,co_4290_fs,co_4290_sg,co_4290_zo,co_4290_rmsocl,co_4290_zpres,co_4290_am_co,co_4290_ovc_co,co_4290_vsf_co,co_4290_vsf_co_error,co_4290_am_ch4,co_4290_ovc_ch4,co_4290_vsf_ch4,co_4290_vsf_ch4_error,co_4290_am_h2o,co_4290_ovc_h2o,co_4290_vsf_h2o,co_4290_vsf_h2o_error,co_4290_am_hdo,co_4290_ovc_hdo,co_4290_vsf_hdo,co_4290_vsf_hdo_error,co_4290_ncbf,co_4290_cfampocl,co_4290_cfperiod,co_4290_cfphase,co_4290_cbf_01,co_4290_cbf_02,co_4290_cbf_03,co_4290_cbf_04,n2o_4395_nit,n2o_4395_cl,n2o_4395_ct,n2o_4395_cc,n2o_4395_fs,n2o_4395_sg,n2o_4395_zo,n2o_4395_rmsocl,n2o_4395_zpres,n2o_4395_am_n2o,n2o_4395_ovc_n2o,n2o_4395_vsf_n2o,n2o_4395_vsf_n2o_error,n2o_4395_am_ch4,n2o_4395_ovc_ch4,n2o_4395_vsf_ch4,n2o_4395_vsf_ch4_error,n2o_4395_am_h2o,n2o_4395_ovc_h2o,n2o_4395_vsf_h2o,n2o_4395_vsf_h2o_error,n2o_4395_am_hdo,n2o_4395_ovc_hdo,n2o_4395_vsf_hdo,n2o_4395_vsf_hdo_error,n2o_4395_ncbf,n2o_4395_cfampocl,n2o_4395_cfperiod,n2o_4395_cfphase,n2o_4395_cbf_01,n2o_4395_cbf_02,n2o_4395_cbf_03,n2o_4395_cbf_04,n2o_4430_nit,n2o_4430_cl,n2o_4430_ct,n2o_4430_cc,n2o_4430_fs,n2o_4430_sg,n2o_4430_zo,n2o_4430_rmsocl,n2o_4430_zpres,n2o_4430_am_n2o,n2o_4430_ovc_n2o,n2o_4430_vsf_n2o,n2o_4430_vsf_n2o_error,n2o_4430_am_ch4,n2o_4430_ovc_ch4,n2o_4430_vsf_ch4,n2o_4430_vsf_ch4_error,n2o_4430_am_h2o,n2o_4430_ovc_h2o,n2o_4430_vsf_h2o,n2o_4430_vsf_h2o_error,n2o_4430_am_hdo,n2o_4430_ovc_hdo,n2o_4430_vsf_hdo,n2o_4430_vsf_hdo_error,n2o_4430_am_co2,n2o_4430_ovc_co2,n2o_4430_vsf_co2,n2o_4430_vsf_co2_error,n2o_4430_ncbf,n2o_4430_cfampocl,n2o_4430_cfperiod,n2o_4430_cfphase,n2o_4430_cbf_01,n2o_4430_cbf_02,n2o_4719_nit,n2o_4719_cl,n2o_4719_ct,n2o_4719_cc,n2o_4719_fs,n2o_4719_sg,n2o_4719_zo,n2o_4719_rmsocl,n2o_4719_zpres,n2o_4719_am_n2o,n2o_4719_ovc_n2o,n2o_4719_vsf_n2o,n2o_4719_vsf_n2o_error,n2o_4719_am_ch4,n2o_4719_ovc_ch4,n2o_4719_vsf_ch4,n2o_4719_vsf_ch4_error,n2o_4719_am_h2o,n2o_4719_ovc_h2o,n2o_4719_vsf_h2o,n2o_4719_vsf_h2o_error,n2o_4719_am_co2,n2o_4719_ovc_co2,n2o_4719_vsf_co2,n2o_4719_vsf_co2_error,n2o_4719_ncbf,n2o_4719_cfampocl,n2o_4719_cfperiod,n2o_4719_cfphase,n2o_4719_cbf_01,n2o_4719_cbf_02,n2o_4719_cbf_03,ch4_5938_nit,ch4_5938_cl,ch4_5938_ct,ch4_5938_cc,ch4_5938_fs,ch4_5938_sg,ch4_5938_zo,ch4_5938_rmsocl,ch4_5938_zpres,ch4_5938_am_ch4,ch4_5938_ovc_ch4,ch4_5938_vsf_ch4,ch4_5938_vsf_ch4_error,ch4_5938_am_co2,ch4_5938_ovc_co2,ch4_5938_vsf_co2,ch4_5938_vsf_co2_error,ch4_5938_am_h2o,ch4_5938_ovc_h2o,ch4_5938_vsf_h2o,ch4_5938_vsf_h2o_error,ch4_5938_am_n2o,ch4_5938_ovc_n2o,ch4_5938_vsf_n2o,ch4_5938_vsf_n2o_error,ch4_5938_ncbf,ch4_5938_cfampocl,ch4_5938_cfperiod,ch4_5938_cfphase,ch4_5938_cbf_01,ch4_5938_cbf_02,ch4_5938_cbf_03,ch4_5938_cbf_04,ch4_6002_nit,ch4_6002_cl,ch4_6002_ct,ch4_6002_cc,ch4_6002_fs,ch4_6002_sg,ch4_6002_zo,ch4_6002_rmsocl,ch4_6002_zpres,ch4_6002_am_ch4,ch4_6002_ovc_ch4,ch4_6002_vsf_ch4,ch4_6002_vsf_ch4_error,ch4_6002_am_co2,ch4_6002_ovc_co2,ch4_6002_vsf_co2,ch4_6002_vsf_co2_error,ch4_6002_am_h2o,ch4_6002_ovc_h2o,ch4_6002_vsf_h2o,ch4_6002_vsf_h2o_error,ch4_6002_am_hdo,ch4_6002_ovc_hdo,ch4_6002_vsf_hdo,ch4_6002_vsf_hdo_error,ch4_6002_ncbf,ch4_6002_cfampocl,ch4_6002_cfperiod,ch4_6002_cfphase,ch4_6002_cbf_01,ch4_6002_cbf_02,ch4_6076_nit,ch4_6076_cl,ch4_6076_ct,ch4_6076_cc,ch4_6076_fs,ch4_6076_sg,ch4_6076_zo,ch4_6076_rmsocl,ch4_6076_zpres,ch4_6076_am_ch4,ch4_6076_ovc_ch4,ch4_6076_vsf_ch4,ch4_6076_vsf_ch4_error,ch4_6076_am_co2,ch4_6076_ovc_co2,ch4_6076_vsf_co2,ch4_6076_vsf_co2_error,ch4_6076_am_h2o,ch4_6076_ovc_h2o,ch4_6076_vsf_h2o,ch4_6076_vsf_h2o_error,ch4_6076_am_hdo,ch4_6076_ovc_hdo,ch4_6076_vsf_hdo,ch4_6076_vsf_hdo_error,ch4_6076_ncbf,ch4_6076_cfampocl,ch4_6076_cfperiod,ch4_6076_cfphase,ch4_6076_cbf_01,ch4_6076_cbf_02,ch4_6076_cbf_03,ch4_6076_cbf_04,ch4_6076_cbf_05,lco2_4852_nit,lco2_4852_cl,lco2_4852_ct,lco2_4852_cc,lco2_4852_fs,lco2_4852_sg,lco2_4852_zo,lco2_4852_rmsocl,lco2_4852_zpres,lco2_4852_am_lco2,lco2_4852_ovc_lco2,lco2_4852_vsf_lco2,lco2_4852_vsf_lco2_error,lco2_4852_am_2co2,lco2_4852_ovc_2co2,lco2_4852_vsf_2co2,lco2_4852_vsf_2co2_error,lco2_4852_am_3co2,lco2_4852_ovc_3co2,lco2_4852_vsf_3co2,lco2_4852_vsf_3co2_error,lco2_4852_am_4co2,lco2_4852_ovc_4co2,lco2_4852_vsf_4co2,lco2_4852_vsf_4co2_error,lco2_4852_am_h2o,lco2_4852_ovc_h2o,lco2_4852_vsf_h2o,lco2_4852_vsf_h2o_error,lco2_4852_am_hdo,lco2_4852_ovc_hdo,lco2_4852_vsf_hdo,lco2_4852_vsf_hdo_error,lco2_4852_ncbf,lco2_4852_cfampocl,lco2_4852_cfperiod,lco2_4852_cfphase,lco2_4852_cbf_01,lco2_4852_cbf_02,lco2_4852_cbf_03,zco2_4852_nit,zco2_4852_cl,zco2_4852_ct,zco2_4852_cc,zco2_4852_fs,zco2_4852_sg,zco2_4852_zo,zco2_4852_rmsocl,zco2_4852_zpres,zco2_4852_am_zco2,zco2_4852_ovc_zco2,zco2_4852_vsf_zco2,zco2_4852_vsf_zco2_error,zco2_4852_am_h2o,zco2_4852_ovc_h2o,zco2_4852_vsf_h2o,zco2_4852_vsf_h2o_error,zco2_4852_am_hdo,zco2_4852_ovc_hdo,zco2_4852_vsf_hdo,zco2_4852_vsf_hdo_error,zco2_4852_ncbf,zco2_4852_cfampocl,zco2_4852_cfperiod,zco2_4852_cfphase,zco2_4852_cbf_01,zco2_4852_cbf_02,zco2_4852_cbf_03,zco2_4852a_nit,zco2_4852a_cl,zco2_4852a_ct,zco2_4852a_cc,zco2_4852a_fs,zco2_4852a_sg,zco2_4852a_zo,zco2_4852a_rmsocl,zco2_4852a_zpres,zco2_4852a_am_zco2,zco2_4852a_ovc_zco2,zco2_4852a_vsf_zco2,zco2_4852a_vsf_zco2_error,zco2_4852a_am_h2o,zco2_4852a_ovc_h2o,zco2_4852a_vsf_h2o,zco2_4852a_vsf_h2o_error,zco2_4852a_am_hdo,zco2_4852a_ovc_hdo,zco2_4852a_vsf_hdo,zco2_4852a_vsf_hdo_error,zco2_4852a_ncbf,zco2_4852a_cfampocl,zco2_4852a_cfperiod,zco2_4852a_cfphase,zco2_4852a_cbf_01,zco2_4852a_cbf_02,zco2_4852a_cbf_03,fco2_6154_nit,fco2_6154_cl,fco2_6154_ct,fco2_6154_cc,fco2_6154_fs,fco2_6154_sg,fco2_6154_zo,fco2_6154_rmsocl,fco2_6154_zpres,fco2_6154_am_fco2,fco2_6154_ovc_fco2,fco2_6154_vsf_fco2,fco2_6154_vsf_fco2_error,fco2_6154_am_h2o,fco2_6154_ovc_h2o,fco2_6154_vsf_h2o,fco2_6154_vsf_h2o_error,fco2_6154_am_hdo,fco2_6154_ovc_hdo,fco2_6154_vsf_hdo,fco2_6154_vsf_hdo_error,fco2_6154_am_ch4,fco2_6154_ovc_ch4,fco2_6154_vsf_ch4,fco2_6154_vsf_ch4_error,fco2_6154_ncbf,fco2_6154_cfampocl,fco2_6154_cfperiod,fco2_6154_cfphase,fco2_6154_cbf_01,fco2_6154_cbf_02,fco2_6154_cbf_03,fco2_6154_cbf_04,wco2_6073_nit,wco2_6073_cl,wco2_6073_ct,wco2_6073_cc,wco2_6073_fs,wco2_6073_sg,wco2_6073_zo,wco2_6073_rmsocl,wco2_6073_zpres,wco2_6073_am_wco2,wco2_6073_ovc_wco2,wco2_6073_vsf_wco2,wco2_6073_vsf_wco2_error,wco2_6073_am_h2o,wco2_6073_ovc_h2o,wco2_6073_vsf_h2o,wco2_6073_vsf_h2o_error,wco2_6073_am_ch4,wco2_6073_ovc_ch4,wco2_6073_vsf_ch4,wco2_6073_vsf_ch4_error,wco2_6073_ncbf,wco2_6073_cfampocl,wco2_6073_cfperiod,wco2_6073_cfphase,wco2_6073_cbf_01,wco2_6073_cbf_02,co2_6220_nit,co2_6220_cl,co2_6220_ct,co2_6220_cc,co2_6220_fs,co2_6220_sg,co2_6220_zo,co2_6220_rmsocl,co2_6220_zpres,co2_6220_am_co2,co2_6220_ovc_co2,co2_6220_vsf_co2,co2_6220_vsf_co2_error,co2_6220_am_h2o,co2_6220_ovc_h2o,co2_6220_vsf_h2o,co2_6220_vsf_h2o_error,co2_6220_am_hdo,co2_6220_ovc_hdo,co2_6220_vsf_hdo,co2_6220_vsf_hdo_error,co2_6220_am_ch4,co2_6220_ovc_ch4,co2_6220_vsf_ch4,co2_6220_vsf_ch4_error,co2_6220_ncbf,co2_6220_cfampocl,co2_6220_cfperiod,co2_6220_cfphase,co2_6220_cbf_01,co2_6220_cbf_02,co2_6220_cbf_03,co2_6339_nit,co2_6339_cl,co2_6339_ct,co2_6339_cc,co2_6339_fs,co2_6339_sg,co2_6339_zo,co2_6339_rmsocl,co2_6339_zpres,co2_6339_am_co2,co2_6339_ovc_co2,co2_6339_vsf_co2,co2_6339_vsf_co2_error,co2_6339_am_h2o,co2_6339_ovc_h2o,co2_6339_vsf_h2o,co2_6339_vsf_h2o_error,co2_6339_am_hdo,co2_6339_ovc_hdo,co2_6339_vsf_hdo,co2_6339_vsf_hdo_error,co2_6339_ncbf,co2_6339_cfampocl,co2_6339_cfperiod,co2_6339_cfphase,co2_6339_cbf_01,co2_6339_cbf_02,co2_6339_cbf_03,o2_7885_nit,o2_7885_cl,o2_7885_ct,o2_7885_cc,o2_7885_fs,o2_7885_sg,o2_7885_zo,o2_7885_rmsocl,o2_7885_zpres,o2_7885_am_o2,o2_7885_ovc_o2,o2_7885_vsf_o2,o2_7885_vsf_o2_error,o2_7885_am_0o2,o2_7885_ovc_0o2,o2_7885_vsf_0o2,o2_7885_vsf_0o2_error,o2_7885_am_h2o,o2_7885_ovc_h2o,o2_7885_vsf_h2o,o2_7885_vsf_h2o_error,o2_7885_am_hf,o2_7885_ovc_hf,o2_7885_vsf_hf,o2_7885_vsf_hf_error,o2_7885_am_co2,o2_7885_ovc_co2,o2_7885_vsf_co2,o2_7885_vsf_co2_error,o2_7885_am_hdo,o2_7885_ovc_hdo,o2_7885_vsf_hdo,o2_7885_vsf_hdo_error,o2_7885_ncbf,o2_7885_cfampocl,o2_7885_cfperiod,o2_7885_cfphase,o2_7885_cbf_01,o2_7885_cbf_02,o2_7885_cbf_03,o2_7885_cbf_04,o2_7885_cbf_05,hcl_5625_nit,hcl_5625_cl,hcl_5625_ct,hcl_5625_cc,hcl_5625_fs,hcl_5625_sg,hcl_5625_zo,hcl_5625_rmsocl,hcl_5625_zpres,hcl_5625_am_hcl,hcl_5625_ovc_hcl,hcl_5625_vsf_hcl,hcl_5625_vsf_hcl_error,hcl_5625_am_h2o,hcl_5625_ovc_h2o,hcl_5625_vsf_h2o,hcl_5625_vsf_h2o_error,hcl_5625_am_ch4,hcl_5625_ovc_ch4,hcl_5625_vsf_ch4,hcl_5625_vsf_ch4_error,hcl_5625_ncbf,hcl_5625_cfampocl,hcl_5625_cfperiod,hcl_5625_cfphase,hcl_5625_cbf_01,hcl_5625_cbf_02,hcl_5687_nit,hcl_5687_cl,hcl_5687_ct,hcl_5687_cc,hcl_5687_fs,hcl_5687_sg,hcl_5687_zo,hcl_5687_rmsocl,hcl_5687_zpres,hcl_5687_am_hcl,hcl_5687_ovc_hcl,hcl_5687_vsf_hcl,hcl_5687_vsf_hcl_error,hcl_5687_am_h2o,hcl_5687_ovc_h2o,hcl_5687_vsf_h2o,hcl_5687_vsf_h2o_error,hcl_5687_am_ch4,hcl_5687_ovc_ch4,hcl_5687_vsf_ch4,hcl_5687_vsf_ch4_error,hcl_5687_ncbf,hcl_5687_cfampocl,hcl_5687_cfperiod,hcl_5687_cfphase,hcl_5687_cbf_01,hcl_5687_cbf_02,hcl_5702_nit,hcl_5702_cl,hcl_5702_ct,hcl_5702_cc,hcl_5702_fs,hcl_5702_sg,hcl_5702_zo,hcl_5702_rmsocl,hcl_5702_zpres,hcl_5702_am_hcl,hcl_5702_ovc_hcl,hcl_5702_vsf_hcl,hcl_5702_vsf_hcl_error,hcl_5702_am_h2o,hcl_5702_ovc_h2o,hcl_5702_vsf_h2o,hcl_5702_vsf_h2o_error,hcl_5702_am_ch4,hcl_5702_ovc_ch4,hcl_5702_vsf_ch4,hcl_5702_vsf_ch4_error,hcl_5702_ncbf,hcl_5702_cfampocl,hcl_5702_cfperiod,hcl_5702_cfphase,hcl_5702_cbf_01,hcl_5702_cbf_02,hcl_5735_nit,hcl_5735_cl,hcl_5735_ct,hcl_5735_cc,hcl_5735_fs,hcl_5735_sg,hcl_5735_zo,hcl_5735_rmsocl,hcl_5735_zpres,hcl_5735_am_hcl,hcl_5735_ovc_hcl,hcl_5735_vsf_hcl,hcl_5735_vsf_hcl_error,hcl_5735_am_h2o,hcl_5735_ovc_h2o,hcl_5735_vsf_h2o,hcl_5735_vsf_h2o_error,hcl_5735_am_ch4,hcl_5735_ovc_ch4,hcl_5735_vsf_ch4,hcl_5735_vsf_ch4_error,hcl_5735_ncbf,hcl_5735_cfampocl,hcl_5735_cfperiod,hcl_5735_cfphase,hcl_5735_cbf_01,hcl_5735_cbf_02,hcl_5739_nit,hcl_5739_cl,hcl_5739_ct,hcl_5739_cc,hcl_5739_fs,hcl_5739_sg,hcl_5739_zo,hcl_5739_rmsocl,hcl_5739_zpres,hcl_5739_am_hcl,hcl_5739_ovc_hcl,hcl_5739_vsf_hcl,hcl_5739_vsf_hcl_error,hcl_5739_am_h2o,hcl_5739_ovc_h2o,hcl_5739_vsf_h2o,hcl_5739_vsf_h2o_error,hcl_5739_am_ch4,hcl_5739_ovc_ch4,hcl_5739_vsf_ch4,hcl_5739_vsf_ch4_error,hcl_5739_ncbf,hcl_5739_cfampocl,hcl_5739_cfperiod,hcl_5739_cfphase,hcl_5739_cbf_01,hcl_5739_cbf_02,luft_6146_nit,luft_6146_cl,luft_6146_ct,luft_6146_cc,luft_6146_fs,luft_6146_sg,luft_6146_zo,luft_6146_rmsocl,luft_6146_zpres,luft_6146_am_luft,luft_6146_ovc_luft,luft_6146_vsf_luft,luft_6146_vsf_luft_error,luft_6146_ncbf,luft_6146_cfampocl,luft_6146_cfperiod,luft_6146_cfphase";
 
 
-fn read_windows_table() -> (HashMap<String, Window>, Vec<String>) {
+fn read_windows_table(release: &Release) -> (HashMap<String, Window<'static>>, Vec<String>) {
+    // Like the correction tables, the embedded windows table must always parse
+    return parse_windows_table(release.windows_table).unwrap();
+}
+
+fn _is_window_line(line: &str) -> bool {
+    let mut parts = line.split(':');
+    let has_center = parts.next().is_some_and(|params| !params.trim().is_empty());
+    let has_gases = parts.next().is_some_and(|gases| !gases.trim().is_empty());
+    has_center && has_gases
+}
+
+fn parse_windows_table(table: &str) -> Result<(HashMap<String, Window<'_>>, Vec<String>), CheckError> {
     lazy_static! {
         static ref RE: Regex = Regex::new(r"sf=(\d\.\d+)").unwrap();
     }
     let mut windows = HashMap::new();
     let mut skipped_windows = Vec::new();
     let mut first_line = true;
-    for line in WINDOWS_TABLE.split("\n") {
+    for line in table.split("\n") {
         if first_line {
             first_line = false;
             
-        }else if line.starts_with(':') {
-            let (win_name, _, _) = get_window_name(&line[1..]);
+        }else if line.trim().is_empty() {
+            continue;
+        }else if !_is_window_line(line.strip_prefix(':').unwrap_or(line)) {
+            return Err(CheckError::TableParse(String::from(line), String::from("expected the window parameters, a colon, and the gases to fit")));
+        }else if let Some(stripped) = line.strip_prefix(':') {
+            let (win_name, _, _) = get_window_name(stripped);
             skipped_windows.push(win_name);
         }else{
 
@@ -227,25 +563,36 @@ fn read_windows_table() -> (HashMap<String, Window>, Vec<String>) {
             };
             
             let s = Window{
-                center: center_str.parse::<i32>().unwrap(),
+                center: _parse_table_value(center_str, line)?,
                 gas: main_gas,
-                sf: sf
+                sf: sf,
+                params: get_window_params(line),
+                gases: line.split(':').nth(1).unwrap().split_whitespace().collect()
             };
 
-            windows.insert(win_name, s);
+            // GGG tells apart windows with the same gas and center (e.g. the two
+            // zco2 4852 windows) by appending a letter to the later ones
+            let mut unique_name = win_name.clone();
+            let mut suffix = b'a';
+            while windows.contains_key(&unique_name) {
+                unique_name = format!("{}{}", win_name, suffix as char);
+                suffix += 1;
+            }
+
+            windows.insert(unique_name, s);
         }
     }
 
     // Remove any skipped windows that also show up in windows, as those were
     // probably commented out because they conflict
     skipped_windows.retain(|el| !windows.contains_key(el));
-    return (windows, skipped_windows);
+    return Ok((windows, skipped_windows));
 }
 
-fn get_window_name(table_line: &'static str) -> (String, &'static str, &'static str) {
-    let tmp: Vec<&'static str> = table_line.split(':').into_iter().collect();
-    let cmd: Vec<&'static str> = tmp[0].split_whitespace().into_iter().collect();
-    let gases: Vec<&'static str> = tmp[1].split_whitespace().into_iter().collect();
+fn get_window_name(table_line: &str) -> (String, &str, &str) {
+    let tmp: Vec<&str> = table_line.split(':').collect();
+    let cmd: Vec<&str> = tmp[0].split_whitespace().collect();
+    let gases: Vec<&str> = tmp[1].split_whitespace().collect();
 
     let center_str = cmd[0].split('.').next().unwrap();
     let main_gas = gases[0];
@@ -254,11 +601,48 @@ fn get_window_name(table_line: &'static str) -> (String, &'static str, &'static
     return (window_name, main_gas, center_str)
 }
 
+fn get_window_params(table_line: &str) -> Vec<&str> {
+    // The first six columns are the center, width, MIT, and A/I/F; the scale factor has its own field
+    let cmd = table_line.split(':').next().unwrap();
+    cmd.split_whitespace().skip(6).filter(|p| !p.starts_with("sf=")).collect()
+}
+
 
 // ************* //
 // GENERAL UTILS //
 // ************* //
 
+thread_local! {
+    // A section header not yet printed because nothing has been reported in its section
+    static PENDING_HEADER: std::cell::Cell<Option<&'static str>> = const { std::cell::Cell::new(None) };
+}
+
+// Every line a check prints goes through this, so that a pending header is printed just before
+// the first line of its section
+macro_rules! report {
+    ($($arg:tt)*) => {{
+        _flush_pending_header();
+        println!($($arg)*);
+    }};
+}
+
+fn _print_header(header: &'static str, clargs: &CmdLineArgs) {
+    // With --failures-only, most sections of a good file print nothing, so their headers
+    // are held back until there is something to print under them
+    if clargs.failures_only {
+        PENDING_HEADER.with(|h| h.set(Some(header)));
+    }else{
+        PENDING_HEADER.with(|h| h.set(None));
+        println!("{}", header);
+    }
+}
+
+fn _flush_pending_header() {
+    if let Some(header) = PENDING_HEADER.with(|h| h.take()) {
+        println!("{}", header);
+    }
+}
+
 /* Verbosity levels:
 
    -1 = no messages, just indicate by exit code
@@ -266,14 +650,20 @@ fn get_window_name(table_line: &'static str) -> (String, &'static str, &'static
     1 = print for each category
     2 = print for each gas/window
     3 = print for each variable
+
+   Output order is deterministic, so that results can be diffed across runs:
+   items from the tables (gases, windows) are reported sorted by name, items from
+   a fixed list (e.g. the expected InGaAs variables) in the order of that list, and
+   items found by scanning the file (e.g. the <window>_fs variables) sorted by name,
+   so that the order of variables within the file does not matter.
  */
-fn _check_float_variable(nch: &netcdf::File, varname: &str, expected_value: f32, missing_ok: bool, clargs: &CmdLineArgs) -> Result<bool, String> {
+fn _check_float_variable(nch: &netcdf::File, varname: &str, expected_value: f32, missing_ok: bool, clargs: &CmdLineArgs) -> Result<bool, CheckError> {
     let nc_data = match _get_var(nch, varname) {
         Ok(data) => data,
         Err(err) => {
             if missing_ok {
                 if clargs.verbosity == 3 {
-                    println!("    - FAIL: variable '{}' is missing", varname);
+                    report!("    - FAIL: variable '{}' is missing", varname);
                 }
                 return Ok(false);
             }else{
@@ -287,61 +677,312 @@ fn _check_float_variable(nch: &netcdf::File, varname: &str, expected_value: f32,
 }
 
 
-fn _get_var<'a>(nch: &'a netcdf::File, varname: &str) -> Result<netcdf::Variable<'a>, String> {
+fn _get_var<'a>(nch: &'a netcdf::File, varname: &str) -> Result<netcdf::Variable<'a>, CheckError> {
     match nch.variable(varname) {
         Some(v) => return Ok(v),
-        None => return Err(format!("Could not read variable '{}'", varname))
+        None => return Err(CheckError::VariableMissing(String::from(varname)))
     }
 }
 
+fn _format_percent(n_wrong: usize, n_total: usize) -> String {
+    // Never round a non-zero number of failures to 0.00%, or a partial failure to 100.00%
+    let percent = n_wrong as f64 / n_total.max(1) as f64 * 100.0;
+    if n_wrong > 0 && percent < 0.005 {
+        return String::from("<0.01%");
+    }else if n_wrong < n_total && percent >= 99.995 {
+        return String::from(">99.99%");
+    }
+    return format!("{:.2}%", percent);
+}
+
 fn _print_variable_results(varname: &str, n_total: usize, n_wrong: usize, clargs: &CmdLineArgs) -> bool {
     let is_ok = n_wrong == 0;
     if is_ok {
         if clargs.verbosity >= 3 && !clargs.failures_only{
-            println!("    - PASS: {}", varname);
+            report!("    - PASS: {}", varname);
         }
     } else {
         if clargs.verbosity >= 3 {
-            let percent = n_wrong as f32 / n_total as f32 * 100.0;
-            println!("    - FAIL: {}/{} ({:.2}%) of {} have incorrect values", n_wrong, n_total, percent, varname);
+            report!("    - FAIL: {}/{} ({}) of {} have incorrect values", n_wrong, n_total, _format_percent(n_wrong, n_total), varname);
         }
     }
 
     return is_ok;
 }
 
-fn _all_equal_float(var: &netcdf::Variable, expected_value: f32, clargs: &CmdLineArgs) -> Result<bool, String> {
-    let data = match var.values::<f32>(None, None) {
-        Ok(arr) => arr,
-        Err(err) => return Err(format!("Could not get data of '{}' variable: {}", var.name(), err))
-    };
-
+fn _all_equal_float(var: &netcdf::Variable, expected_value: f32, clargs: &CmdLineArgs) -> Result<bool, CheckError> {
+    let data = _get_record_data(var, clargs)?;
     let n_total = data.len();
-    let mut n_wrong: usize = 0;
+    let varname = var.name();
+    let n_wrong = _count_not_equal(&data, expected_value, _comparison_epsilon(&varname, clargs));
 
-    for &value in data.iter() {
-        // The ADCFs and AICFs are only written to 4 decimal places in the .aia file
-        if !value.approx_eq(expected_value, F32Margin{ ulps: 1, epsilon: 1e-4}) {
-            n_wrong += 1;
+    let is_ok = _print_variable_results(&varname, n_total, n_wrong, clargs);
+    return Ok(is_ok)
+}
+
+// The ADCFs and AICFs are only written to 4 decimal places in the .aia file
+const DEFAULT_EPSILON: f32 = 1e-4;
+
+fn _comparison_epsilon(varname: &str, clargs: &CmdLineArgs) -> f32 {
+    // --tolerance keys may be the whole variable name or its gas prefix, with or without
+    // the leading x (e.g. xco2 or co2 for xco2_6220_adcf or vsw_sf_co2_6220); the first match
+    // in that order wins. Window scale factors have the gas after their vsw_sf_ prefix.
+    let prefix = varname.strip_prefix("vsw_sf_").unwrap_or(varname).split('_').next().unwrap();
+    let other_form = match prefix.strip_prefix('x') {
+        Some(gas) => gas.to_owned(),
+        None => format!("x{}", prefix)
+    };
+    for key in [varname, prefix, &other_form].iter() {
+        if let Some((_, eps)) = clargs.tolerances.iter().find(|(k, _)| k == key) {
+            return *eps;
         }
     }
+    return DEFAULT_EPSILON;
+}
 
-    
+fn _not_equal_indices(data: &[f32], expected_value: f32, epsilon: f32) -> Vec<usize> {
+    data.iter()
+        .enumerate()
+        .filter(|(_, &value)| !value.approx_eq(expected_value, F32Margin{ ulps: 1, epsilon: epsilon}))
+        .map(|(i, _)| i)
+        .collect()
+}
+
+fn _count_not_equal(data: &[f32], expected_value: f32, epsilon: f32) -> usize {
+    _not_equal_indices(data, expected_value, epsilon).len()
+}
+
+fn _check_float_range_variable(nch: &netcdf::File, varname: &str, min: Option<f32>, max: Option<f32>, missing_ok: bool, clargs: &CmdLineArgs) -> Result<bool, CheckError> {
+    let nc_data = match _get_var(nch, varname) {
+        Ok(data) => data,
+        Err(err) => {
+            if missing_ok {
+                if clargs.verbosity == 3 {
+                    report!("    - FAIL: variable '{}' is missing", varname);
+                }
+                return Ok(false);
+            }else{
+                return Err(err);
+            }
+        }
+    };
+
+    return _all_in_range_float(&nc_data, min, max, clargs);
+}
+
+fn _all_in_range_float(var: &netcdf::Variable, min: Option<f32>, max: Option<f32>, clargs: &CmdLineArgs) -> Result<bool, CheckError> {
+    let (n_total, n_wrong) = _count_out_of_range(var, min, max, clargs)?;
     let is_ok = _print_variable_results(&var.name(), n_total, n_wrong, clargs);
     return Ok(is_ok)
 }
 
-fn _get_string_attribute_value(nch: &netcdf::File, att_name: &str, clargs: &CmdLineArgs) -> Result<String, String> {
+fn _count_out_of_range(var: &netcdf::Variable, min: Option<f32>, max: Option<f32>, clargs: &CmdLineArgs) -> Result<(usize, usize), CheckError> {
+    let data = _get_record_data(var, clargs)?;
+    return Ok((data.len(), _count_outside(&data, min, max)));
+}
+
+fn _count_outside(data: &[f32], min: Option<f32>, max: Option<f32>) -> usize {
+    // A missing bound means that side is unlimited; NaNs are always out of range
+    data.iter()
+        .filter(|&&value| {
+            let too_low = matches!(min, Some(m) if value < m);
+            let too_high = matches!(max, Some(m) if value > m);
+            value.is_nan() || too_low || too_high
+        })
+        .count()
+}
+
+fn _get_variable_names(nch: &netcdf::File) -> HashSet<String> {
+    return nch.variables().map(|var| var.name()).collect();
+}
+
+fn _get_float_data(var: &netcdf::Variable, clargs: &CmdLineArgs) -> Result<Vec<f32>, CheckError> {
+    _check_memory_budget(var, &_full_shape(var), std::mem::size_of::<f32>(), clargs)?;
+    match var.values::<f32>(None, None) {
+        Ok(arr) => Ok(arr.iter().copied().collect()),
+        Err(err) => Err(CheckError::VariableRead(var.name(), err.to_string()))
+    }
+}
+
+fn _is_record_dim(dim: &netcdf::Dimension) -> bool {
+    // The per-spectrum records and the priors they use; every other dimension (levels, cells, ...) is a grid
+    let name = dim.name();
+    name == "time" || name == "prior_time"
+}
+
+fn _first_records_shape(var: &netcdf::Variable, n_records: usize) -> Vec<usize> {
+    var.dimensions().iter().map(|d| if _is_record_dim(d) {d.len().min(n_records)} else {d.len()}).collect()
+}
+
+fn _get_first_records(var: &netcdf::Variable, n_records: usize, clargs: &CmdLineArgs) -> Result<Vec<f32>, CheckError> {
+    // Reads only the hyperslab of the first n_records along the record dimensions
+    let count = _first_records_shape(var, n_records);
+    _check_memory_budget(var, &count, std::mem::size_of::<f32>(), clargs)?;
+    let start = vec![0; count.len()];
+    match var.values::<f32>(Some(&start), Some(&count)) {
+        Ok(arr) => Ok(arr.iter().copied().collect()),
+        Err(err) => Err(CheckError::VariableRead(var.name(), err.to_string()))
+    }
+}
+
+fn _full_shape(var: &netcdf::Variable) -> Vec<usize> {
+    var.dimensions().iter().map(|d| d.len()).collect()
+}
+
+fn _check_memory_budget(var: &netcdf::Variable, shape: &[usize], value_size: usize, clargs: &CmdLineArgs) -> Result<(), CheckError> {
+    // Values are read into a Vec (and briefly copied), so error before a read of this shape would exceed --max-memory
+    let max_mb = match clargs.max_memory_mb {
+        Some(m) => m,
+        None => return Ok(())
+    };
+    let needed_mb = (shape.iter().product::<usize>() * value_size * 2) as f64 / 1048576.0;
+    if needed_mb > max_mb as f64 {
+        let hint = if var.dimensions().iter().any(_is_record_dim) {"; use --first-n to read only the first records"} else {""};
+        return Err(CheckError::Other(format!(
+            "Reading {} would need about {:.0} MB, more than --max-memory {}{}",
+            var.name(), needed_mb, max_mb, hint
+        )));
+    }
+    return Ok(());
+}
+
+fn _is_masked_by(var: &netcdf::Variable, mask: &[bool]) -> bool {
+    matches!(var.dimensions().first(), Some(d) if d.name() == "time" && d.len() == mask.len())
+}
+
+fn _file_record_index(var: &netcdf::Variable, i: usize, clargs: &CmdLineArgs) -> usize {
+    // Maps an index into _get_record_data's values back to the record in the file, which
+    // differs when --since dropped earlier records
+    match &clargs.record_mask {
+        Some(mask) if _is_masked_by(var, mask) => mask.iter().enumerate().filter(|(_, &keep)| keep).nth(i).map_or(i, |(j, _)| j),
+        _ => i
+    }
+}
+
+fn _get_record_data(var: &netcdf::Variable, clargs: &CmdLineArgs) -> Result<Vec<f32>, CheckError> {
+    // With --first-n, only the first records are read at all. With --since, only keep the records
+    // at or after that time. Variables without time as their first dimension are not per-record,
+    // so are always kept whole by --since.
+    let data = match clargs.first_n {
+        Some(n) if var.dimensions().iter().any(_is_record_dim) => _get_first_records(var, n, clargs)?,
+        _ => _get_float_data(var, clargs)?
+    };
+    let mask = match &clargs.record_mask {
+        Some(m) => m,
+        None => return Ok(data)
+    };
+
+    if !_is_masked_by(var, mask) || data.is_empty() {
+        return Ok(data);
+    }
+
+    // Computed from the dimensions rather than the data, since --first-n may have truncated it
+    let per_record: usize = var.dimensions()[1..].iter().map(|d| d.len()).product();
+    let kept = data.chunks(per_record)
+        .zip(mask.iter())
+        .filter(|(_, &keep)| keep)
+        .flat_map(|(record, _)| record.iter().copied())
+        .collect();
+    return Ok(kept);
+}
+
+fn _parse_datetime(value: &str) -> Option<NaiveDateTime> {
+    let value = value.trim().trim_end_matches("UTC").trim_end_matches('Z').trim();
+    for fmt in ["%Y-%m-%dT%H:%M:%S", "%Y-%m-%d %H:%M:%S", "%Y-%m-%dT%H:%M", "%Y-%m-%d %H:%M"].iter() {
+        if let Ok(dt) = NaiveDateTime::parse_from_str(value, fmt) {
+            return Some(dt);
+        }
+    }
+    return NaiveDate::parse_from_str(value, "%Y-%m-%d").ok().and_then(|d| d.and_hms_opt(0, 0, 0));
+}
+
+fn _parse_time_units(units: &str) -> Option<(f64, NaiveDateTime)> {
+    // CF-style units, e.g. "seconds since 1970-01-01 00:00:00"
+    let (unit, epoch) = units.split_once(" since ")?;
+    let unit_seconds = match unit.trim() {
+        "seconds" | "second" | "s" => 1.0,
+        "minutes" | "minute" => 60.0,
+        "hours" | "hour" | "h" => 3600.0,
+        "days" | "day" | "d" => 86400.0,
+        _ => return None
+    };
+    return Some((unit_seconds, _parse_datetime(epoch)?));
+}
+
+fn _compute_record_mask(nch: &netcdf::File, since: &NaiveDateTime, clargs: &CmdLineArgs) -> Result<Vec<bool>, CheckError> {
+    let time_var = _get_var(nch, "time")?;
+    let units = match _get_units(&time_var) {
+        Some(u) => u,
+        None => return Err(CheckError::Other(String::from("The time variable has no units, so --since cannot be applied")))
+    };
+    let (unit_seconds, epoch) = match _parse_time_units(&units) {
+        Some(v) => v,
+        None => return Err(CheckError::Other(format!("Could not parse the time units '{}', so --since cannot be applied", units)))
+    };
+
+    let since_value = (*since - epoch).num_milliseconds() as f64 / 1000.0 / unit_seconds;
+    _check_memory_budget(&time_var, &_full_shape(&time_var), std::mem::size_of::<f64>(), clargs)?;
+    let times = match time_var.values::<f64>(None, None) {
+        Ok(arr) => arr,
+        Err(err) => return Err(CheckError::VariableRead(String::from("time"), err.to_string()))
+    };
+
+    return Ok(times.iter().map(|&t| t >= since_value).collect());
+}
+
+fn _get_fill_value(var: &netcdf::Variable) -> Option<f32> {
+    // Only float variables have a float fill value; anything else just has no fill to exclude
+    var.fill_value::<f32>().ok().flatten()
+}
+
+fn _is_fill_or_nan(value: f32, fill: Option<f32>) -> bool {
+    value.is_nan() || fill == Some(value)
+}
+
+fn _get_units(var: &netcdf::Variable) -> Option<String> {
+    match var.attribute("units").map(|a| a.value()) {
+        Some(Ok(netcdf::AttrValue::Str(s))) => Some(s),
+        _ => None
+    }
+}
+
+fn _first_non_increasing(values: &[f32]) -> Option<usize> {
+    // Returns the index of the first value not strictly greater than the one before it
+    // (a NaN on either side counts as out of order)
+    (1..values.len()).find(|&i| values[i].partial_cmp(&values[i-1]) != Some(std::cmp::Ordering::Greater))
+}
+
+fn _print_bounded_results(varname: &str, n_total: usize, n_wrong: usize, bounds: &str, clargs: &CmdLineArgs) -> bool {
+    // Like _print_variable_results, but for the optional checks where the variable is the
+    // item being reported, so this prints at verbosity 2 and says what the bounds were
+    let is_ok = n_wrong == 0;
+    if is_ok {
+        if clargs.verbosity >= 2 && !clargs.failures_only {
+            report!("  - PASS: all {} values are within {}", varname, bounds);
+        }
+    }else{
+        if clargs.verbosity >= 2 {
+            report!("  - FAIL: {}/{} ({}) of {} values are outside {}", n_wrong, n_total, _format_percent(n_wrong, n_total), varname, bounds);
+        }
+    }
+
+    return is_ok;
+}
+
+fn _get_string_attribute_value(nch: &netcdf::File, att_name: &str, clargs: &CmdLineArgs) -> Result<String, CheckError> {
     let att_val = match nch.attribute(att_name) {
         Some(v) => {
             match v.value() {
                 Ok(inner) => inner,
-                Err(err) => return Err(format!("Could not get value for attribute '{}': {}", att_name, err))
+                Err(err) => return Err(CheckError::AttributeRead(String::from(att_name), err.to_string()))
             }
         },
         None => {
+            if clargs.fail_on_missing_attr {
+                return Err(CheckError::AttributeMissing(String::from(att_name)));
+            }
             if clargs.verbosity >= 2 {
-                println!("  - FAIL: attribute '{}' is not present", att_name);
+                report!("  - FAIL: attribute '{}' is not present", att_name);
             }
             return Ok(String::from(ATT_MISSING_STR))
         }
@@ -349,13 +990,39 @@ fn _get_string_attribute_value(nch: &netcdf::File, att_name: &str, clargs: &CmdL
 
     let att_val = match att_val {
         netcdf::AttrValue::Str(s) => s,
-        _ => return Err(format!("Attribute '{}' has an unexpected type (expected string)", att_name))
+        _ => return Err(CheckError::AttributeType(String::from(att_name), "string"))
     };
 
     return Ok(att_val);
 }
 
-fn _check_string_attribute_value(nch: &netcdf::File, att_name: &str, expected_value: &str, clargs: &CmdLineArgs) -> Result<bool, String> {
+fn _get_float_attribute_value(nch: &netcdf::File, att_name: &str) -> Result<Option<f32>, CheckError> {
+    let att_val = match nch.attribute(att_name) {
+        Some(v) => {
+            match v.value() {
+                Ok(inner) => inner,
+                Err(err) => return Err(CheckError::AttributeRead(String::from(att_name), err.to_string()))
+            }
+        },
+        None => return Ok(None)
+    };
+
+    let value = match att_val {
+        netcdf::AttrValue::Float(v) => v,
+        netcdf::AttrValue::Double(v) => v as f32,
+        netcdf::AttrValue::Floats(ref v) if v.len() == 1 => v[0],
+        netcdf::AttrValue::Doubles(ref v) if v.len() == 1 => v[0] as f32,
+        netcdf::AttrValue::Str(ref s) => match s.trim().parse::<f32>() {
+            Ok(v) => v,
+            Err(_) => return Err(CheckError::AttributeType(String::from(att_name), "a number"))
+        },
+        _ => return Err(CheckError::AttributeType(String::from(att_name), "a single float"))
+    };
+
+    return Ok(Some(value));
+}
+
+fn _check_string_attribute_value(nch: &netcdf::File, att_name: &str, expected_value: &str, clargs: &CmdLineArgs) -> Result<bool, CheckError> {
     let att_val = _get_string_attribute_value(nch, att_name, clargs)?;
     if att_val == ATT_MISSING_STR {
         return Ok(false)
@@ -365,17 +1032,17 @@ fn _check_string_attribute_value(nch: &netcdf::File, att_name: &str, expected_va
     if att_ok {
         if !clargs.failures_only{
             if clargs.verbosity == 2 {
-                println!("  - PASS: attribute '{}' has the expected value", att_name);
+                report!("  - PASS: attribute '{}' has the expected value", att_name);
             }else if clargs.verbosity == 3 {
-                println!("  - PASS: attribute '{}' has the expected value ('{}')", att_name, expected_value);
+                report!("  - PASS: attribute '{}' has the expected value ('{}')", att_name, expected_value);
             }
         }
     }else{
         if clargs.verbosity >= 2 {
-            println!("  - FAIL: attribute '{}' has the wrong value", att_name);
+            report!("  - FAIL: attribute '{}' has the wrong value", att_name);
         }
         if clargs.verbosity == 3 {
-            println!("      (expected = '{}', actual = '{}')", expected_value, att_val);
+            report!("      (expected = '{}', actual = '{}')", expected_value, att_val);
         }
     }
 
@@ -387,42 +1054,69 @@ fn _check_string_attribute_value(nch: &netcdf::File, att_name: &str, expected_va
 // CHECK FUNCTIONS //
 // *************** //
 
-fn check_adcfs(nch: &netcdf::File, adcfs: &HashMap<&'static str, Adcf>, clargs: &CmdLineArgs) -> Result<bool, String> {
+fn check_adcfs(nch: &netcdf::File, adcfs: &HashMap<String, Adcf>, clargs: &CmdLineArgs) -> Result<bool, CheckError> {
     let verbosity = clargs.verbosity;
     
     // Get the windows in alphanumeric order
-    let mut windows: Vec<&'static str> = adcfs.keys().map(|x| *x).collect();
+    let mut windows: Vec<&str> = adcfs.keys().map(|x| x.as_ref()).collect();
     windows.sort_unstable();
 
     if verbosity > 1 {
-        println!("=== Checking ADCF values ===");
+        _print_header("=== Checking ADCF values ===", clargs);
     }
 
     let mut all_ok = true;
+    let mut n_failed = 0;
+    let n_windows = windows.len();
     for window in windows {
+        // ADCF windows are named for the Xgas, e.g. xco2_6220
+        if _allowed_missing(window, clargs) && nch.variable(&format!("{}_adcf", window)).is_none() {
+            continue;
+        }
         let win_ok = check_one_adcf(nch, window, adcfs.get(window).unwrap(), clargs)?;
         all_ok = all_ok && win_ok;
+        if !win_ok { n_failed += 1; }
+    }
+
+    if clargs.compact_fail && !all_ok {
+        report!("FAIL adcfs ({}/{} windows)", n_failed, n_windows);
     }
 
     if verbosity == 1 {
         if all_ok {
-            if !clargs.failures_only{ println!("* PASS: ADCFs match expected values") }; 
+            if !clargs.failures_only{ report!("* PASS: ADCFs match expected values") }; 
         }else {
-            println!("* FAIL: ADCFs do not match expected values");
+            report!("* FAIL: ADCFs do not match expected values");
         }
     }
     
     Ok(all_ok)
 }
 
-fn check_one_adcf(nch: &netcdf::File, window: &str, adcf: &Adcf, clargs: &CmdLineArgs) -> Result<bool, String> {
+fn check_one_adcf(nch: &netcdf::File, window: &str, adcf: &Adcf, clargs: &CmdLineArgs) -> Result<bool, CheckError> {
     let verbosity = clargs.verbosity;
 
     if verbosity > 2 {
-        println!("  * Checking {} ADCFS:", window);
+        report!("  * Checking {} ADCFS:", window);
     }
 
-    let adcfs_ok = _check_float_variable(nch, &format!("{}_adcf", window), adcf.adcf, true, clargs)?;
+    // Normally the ADCFs must match the table; with --adcf-from-attr they need only be
+    // uniform and match what the file itself says was applied
+    let adcf_varname = format!("{}_adcf", window);
+    let expected_adcf = if clargs.adcf_from_attr {
+        let att_val = _get_float_attribute_value(nch, &adcf_varname)?;
+        if att_val.is_none() && clargs.verbosity == 3 {
+            report!("    - FAIL: attribute '{}' is missing, so there is no expected ADCF", adcf_varname);
+        }
+        att_val
+    }else{
+        Some(adcf.adcf)
+    };
+
+    let adcfs_ok = match expected_adcf {
+        Some(expected) => _check_float_variable(nch, &adcf_varname, expected, true, clargs)?,
+        None => false
+    };
     let errs_ok = _check_float_variable(nch, &format!("{}_adcf_error", window), adcf.err, true, clargs)?;
     let g_ok = _check_float_variable(nch, &format!("{}_g", window), adcf.g as f32, true, clargs)?;
     let p_ok = _check_float_variable(nch, &format!("{}_p", window), adcf.p as f32, true, clargs)?;
@@ -431,167 +1125,424 @@ fn check_one_adcf(nch: &netcdf::File, window: &str, adcf: &Adcf, clargs: &CmdLin
 
     if verbosity == 2 {
         if all_ok {
-            if !clargs.failures_only{ println!("  - PASS: {} ADCFs are correct", window) };
+            if !clargs.failures_only{ report!("  - PASS: {} ADCFs are correct", window) };
         }else{
-            println!("  - FAIL: {} ADCFS are incorrect", window);
+            report!("  - FAIL: {} ADCFS are incorrect", window);
         }
     }
 
     Ok(all_ok)
 }
 
-fn check_aicfs(nch: &netcdf::File, aicfs: &HashMap<&'static str, Aicf>, clargs: &CmdLineArgs) -> Result<bool, String> {
-    let mut gases: Vec<&'static str> = aicfs.keys().map(|x| *x).collect();
+fn check_aicf_adcf_coverage(nch: &netcdf::File, adcfs: &HashMap<String, Adcf>, aicfs: &HashMap<String, Aicf>, clargs: &CmdLineArgs) -> bool {
+    // Each AICF corrects a product averaged from ADCF-corrected windows, e.g. xco2 from xco2_6220
+    // and xco2_6339, so the file should have the ADCFs for at least one window behind each AICF
+    let mut gas_windows: HashMap<&str, Vec<&str>> = HashMap::new();
+    for window in adcfs.keys() {
+        let gas = window.rsplit_once('_').map(|(g, _)| g).unwrap_or(window);
+        gas_windows.entry(gas).or_default().push(window);
+    }
+
+    let mut gases: Vec<&str> = aicfs.keys().map(|x| x.as_ref()).collect();
     gases.sort_unstable();
 
     if clargs.verbosity > 1 {
-        println!("\n=== Checking AICF values ===");
+        _print_header("\n=== Checking AICF gases have ADCF windows ===", clargs);
     }
 
     let mut all_ok = true;
+    let mut no_table_windows = Vec::new();
     for gas in gases {
-        let gas_ok = check_one_aicf(nch, gas, aicfs.get(gas).unwrap(), clargs)?;
+        let mut windows = match gas_windows.get(gas) {
+            Some(w) => w.clone(),
+            None => {
+                no_table_windows.push(gas);
+                continue;
+            }
+        };
+        windows.sort_unstable();
+
+        let missing: Vec<&str> = windows.iter().copied()
+            .filter(|win| nch.variable(&format!("{}_adcf", win)).is_none() && !_allowed_missing(win, clargs))
+            .collect();
+        let n_present = windows.iter().filter(|win| nch.variable(&format!("{}_adcf", win)).is_some()).count();
+
+        // A gas with none of its windows present has no ADCF-corrected product at all
+        let gas_ok = n_present > 0 && missing.is_empty();
         all_ok = all_ok && gas_ok;
+        if clargs.verbosity >= 2 {
+            if gas_ok {
+                if !clargs.failures_only{ report!("  - PASS: {} has the ADCFs of all {} of its windows", gas, windows.len()) };
+            }else if n_present == 0 {
+                report!("  - FAIL: {} has an AICF but none of the ADCFs of its windows ({}) are present", gas, windows.join(", "));
+            }else{
+                report!("  - FAIL: {} is missing the ADCFs of {}/{} of its windows ({})", gas, missing.len(), windows.len(), missing.join(", "));
+            }
+        }
+    }
+
+    // Products such as xh2o are not airmass corrected, so this is not a failure
+    if !no_table_windows.is_empty() && clargs.verbosity >= 1 {
+        report!("  NOTE: the AICF table has {} but the ADCF table has no windows for them", no_table_windows.join(", "));
     }
 
     if clargs.verbosity == 1 {
         if all_ok {
-            if !clargs.failures_only{ println!("* PASS: AICFs match expected values") };
+            if !clargs.failures_only{ report!("* PASS: Every AICF gas with ADCF windows has their ADCFs present") };
         }else{
-            println!("* FAIL: AICFs do not match expected values");
+            report!("* FAIL: At least one AICF gas is missing the ADCFs of some or all of its windows");
         }
     }
 
-    Ok(all_ok)
+    return all_ok;
+}
+
+fn check_aicfs(nch: &netcdf::File, aicfs: &HashMap<String, Aicf>, clargs: &CmdLineArgs) -> Result<bool, CheckError> {
+    let mut gases: Vec<&str> = aicfs.keys().map(|x| x.as_ref()).collect();
+    gases.sort_unstable();
+
+    if clargs.verbosity > 1 {
+        _print_header("\n=== Checking AICF values ===", clargs);
+    }
+
+    let mut all_ok = true;
+    let mut n_failed = 0;
+    let n_gases = gases.len();
+    for gas in gases {
+        let gas_ok = check_one_aicf(nch, gas, aicfs.get(gas).unwrap(), clargs)?;
+        all_ok = all_ok && gas_ok;
+        if !gas_ok { n_failed += 1; }
+    }
+
+    if clargs.compact_fail && !all_ok {
+        report!("FAIL aicfs ({}/{} gases)", n_failed, n_gases);
+    }
+
+    if clargs.verbosity == 1 {
+        if all_ok {
+            if !clargs.failures_only{ report!("* PASS: AICFs match expected values") };
+        }else{
+            report!("* FAIL: AICFs do not match expected values");
+        }
+    }
+
+    Ok(all_ok)
 }
 
 
-fn check_one_aicf(nch: &netcdf::File, gas: &str, aicf: &Aicf, clargs: &CmdLineArgs) -> Result<bool, String> {
+fn check_one_aicf(nch: &netcdf::File, gas: &str, aicf: &Aicf, clargs: &CmdLineArgs) -> Result<bool, CheckError> {
     // let aicfs_ok = _all_equal_float(&nc_aicfs, aicf.aicf, verbosity)?;
-    let aicfs_ok = _check_float_variable(nch, &format!("{}_aicf", gas), aicf.aicf, true, clargs)?;
-    let errs_ok = _check_float_variable(nch, &format!("{}_aicf_error", gas), aicf.err, true, clargs)?;
+    let aicfs_ok = _check_float_variable(nch, &clargs.aicf_var_template.replace("{gas}", gas), aicf.aicf, true, clargs)?;
+    let errs_ok = _check_float_variable(nch, &clargs.aicf_error_template.replace("{gas}", gas), aicf.err, true, clargs)?;
 
     let all_ok = aicfs_ok && errs_ok;
 
     if clargs.verbosity == 2 {
         if all_ok {
-            if !clargs.failures_only{ println!("  - PASS: {} AICFS are correct", gas) };
+            if !clargs.failures_only{ report!("  - PASS: {} AICFS are correct", gas) };
         }else{
-            println!("  - FAIL: {} AICFS are not correct", gas);
+            report!("  - FAIL: {} AICFS are not correct", gas);
         }
     }
 
     return Ok(all_ok);
 }
 
-fn check_window_scale_factors(nch: &netcdf::File, windows: &HashMap<String, Window>, clargs: &CmdLineArgs) -> Result<bool, String> {
+fn check_error_propagation(nch: &netcdf::File, aicfs: &HashMap<String, Aicf>, clargs: &CmdLineArgs) -> Result<bool, CheckError> {
+    let mut gases: Vec<&str> = aicfs.keys().map(|x| x.as_ref()).collect();
+    gases.sort_unstable();
+
+    if clargs.verbosity > 1 {
+        _print_header("\n=== Checking AICF error propagation ===", clargs);
+    }
+
+    // x<gas> = ada_x<gas> / AICF, so the error scales the same way. Whether the AICF's own
+    // uncertainty is added in quadrature varies, so either is accepted.
+    let tol = clargs.error_prop_tolerance;
+    let bounds = format!("ada_x<gas>_error / aicf to sqrt((ada_x<gas>_error / aicf)^2 + (x<gas> * aicf_error / aicf)^2), +/- {}%", tol * 100.0);
+    let mut all_ok = true;
+    let mut bad_gases = Vec::new();
+    for gas in gases {
+        let varnames = [
+            gas.to_owned(),
+            format!("{}_error", gas),
+            format!("ada_{}_error", gas),
+            clargs.aicf_var_template.replace("{gas}", gas),
+            clargs.aicf_error_template.replace("{gas}", gas)
+        ];
+        if let Some(missing) = varnames.iter().find(|v| nch.variable(v).is_none()) {
+            if clargs.verbosity >= 2 {
+                report!("  - FAIL: cannot check {} error propagation, variable '{}' is not present", gas, missing);
+            }
+            all_ok = false;
+            bad_gases.push(gas);
+            continue;
+        }
+
+        let mut data = Vec::with_capacity(varnames.len());
+        let mut fills = Vec::with_capacity(varnames.len());
+        for varname in varnames.iter() {
+            let var = _get_var(nch, varname)?;
+            fills.push(_get_fill_value(&var));
+            data.push(_get_record_data(&var, clargs)?);
+        }
+        for (varname, d) in varnames.iter().zip(data.iter()).skip(1) {
+            _check_same_length(&varnames[0], data[0].len(), varname, d.len())?;
+        }
+
+        let mut n_valid: usize = 0;
+        let mut n_wrong: usize = 0;
+        for i in 0..data[0].len() {
+            if (0..data.len()).any(|j| _is_fill_or_nan(data[j][i], fills[j])) {
+                continue;
+            }
+            let (x, x_err, ada_err, aicf, aicf_err) = (data[0][i], data[1][i], data[2][i], data[3][i], data[4][i]);
+            n_valid += 1;
+            let lower = ada_err / aicf;
+            let upper = (lower.powi(2) + (x * aicf_err / aicf).powi(2)).sqrt();
+            if x_err < lower * (1.0 - tol) || x_err > upper * (1.0 + tol) {
+                n_wrong += 1;
+            }
+        }
+
+        let gas_ok = _print_bounded_results(&varnames[1], n_valid, n_wrong, &bounds, clargs);
+        all_ok = all_ok && gas_ok;
+        if !gas_ok { bad_gases.push(gas); }
+    }
+
+    if clargs.verbosity == 1 {
+        if all_ok {
+            if !clargs.failures_only{ report!("* PASS: x<gas>_error is consistent with ada_x<gas>_error and the AICF for all gases") };
+        }else{
+            report!("* FAIL: x<gas>_error is not consistent with ada_x<gas>_error and the AICF for {}", bad_gases.join(", "));
+        }
+    }
+
+    Ok(all_ok)
+}
+
+fn check_window_scale_factors(nch: &netcdf::File, windows: &HashMap<String, Window>, clargs: &CmdLineArgs) -> Result<bool, CheckError> {
     let mut win_names: Vec<&str> = windows.keys().map(|x| x.as_ref()).collect();
     win_names.sort_unstable();
 
     if clargs.verbosity > 1 {
-        println!("\n=== Checking window-to-window scale factors ===");
+        _print_header("\n=== Checking window-to-window scale factors ===", clargs);
     }
 
     let mut all_ok = true;
+    let mut n_failed = 0;
+    let n_windows = win_names.len();
     for win in win_names {
+        if clargs.allowed_missing_windows.iter().any(|w| w == win) && nch.variable(&format!("vsw_sf_{}", win)).is_none() {
+            continue;
+        }
         let win_ok = check_one_window_sf(nch, win, windows.get(win).unwrap(), clargs)?;
         all_ok = all_ok && win_ok;
+        if !win_ok { n_failed += 1; }
+    }
+
+    let known_ok = check_sf_windows_known(nch, windows, clargs);
+
+    if clargs.compact_fail && !(all_ok && known_ok) {
+        let unexpected = if known_ok {""} else {", plus unexpected windows"};
+        report!("FAIL window_sfs ({}/{} windows{})", n_failed, n_windows, unexpected);
     }
 
     if clargs.verbosity == 1 {
         if all_ok {
-            if !clargs.failures_only{ println!("* PASS: Window-to-window scale factors match expected values") };
+            if !clargs.failures_only{ report!("* PASS: Window-to-window scale factors match expected values") };
+        }else {
+            report!("* FAIL: Window-to-window scale factors do not match expected values");
+        }
+
+        if known_ok {
+            if !clargs.failures_only{ report!("* PASS: All window-to-window scale factors in the file are for expected windows") };
         }else {
-            println!("* FAIL: Window-to-window scale factors do not match expected values");
+            report!("* FAIL: At least one window-to-window scale factor in the file is for a window not in the expected table");
         }
     }
 
-    Ok(all_ok)
+    Ok(all_ok && known_ok)
+}
+
+fn check_sf_windows_known(nch: &netcdf::File, windows: &HashMap<String, Window>, clargs: &CmdLineArgs) -> bool {
+    // The reverse of checking each table window: any vsw_sf_ variable in the file
+    // whose window is not in the table means the processing used a window we don't expect
+    let mut file_windows: Vec<String> = nch.variables()
+        .filter_map(|var| var.name().strip_prefix("vsw_sf_").map(String::from))
+        .collect();
+    file_windows.sort_unstable();
+
+    let mut all_known = true;
+    for win in file_windows {
+        if !windows.contains_key(&win) {
+            all_known = false;
+            if clargs.verbosity >= 2 {
+                report!("  - FAIL: file has a window-to-window scale factor for '{}', which is not an expected window", win);
+            }
+        }
+    }
+
+    return all_known;
 }
 
-fn check_one_window_sf(nch: &netcdf::File, win_name: &str, window: &Window, clargs: &CmdLineArgs) -> Result<bool, String> {
+fn check_one_window_sf(nch: &netcdf::File, win_name: &str, window: &Window, clargs: &CmdLineArgs) -> Result<bool, CheckError> {
     let nc_sfs = _get_var(nch, &format!("vsw_sf_{}", win_name))?;
     let sfs_ok = _all_equal_float(&nc_sfs, window.sf, clargs)?;
 
     if clargs.verbosity == 2 {
         if sfs_ok {
-            if !clargs.failures_only {println!("  - PASS: {} window-to-window scale factors are correct", win_name)};
+            if !clargs.failures_only {report!("  - PASS: {} window-to-window scale factors are correct", win_name)};
         }else{
-            println!("  - FAIL: {} window-to-window scale factors are not correct", win_name);
+            report!("  - FAIL: {} window-to-window scale factors are not correct", win_name);
         }
     }
 
     return Ok(sfs_ok);
 }
 
-fn check_included_windows(nch: &netcdf::File, windows: &HashMap<String, Window>, skipped_windows: &Vec<String>, clargs: &CmdLineArgs) -> Result<bool, String> {
-    let mut expected_win_vars: Vec<String> = windows.keys().map(|win| format!("vsw_ada_x{}", win)).collect();
+fn check_included_windows(file_vars: &HashSet<String>, windows: &HashMap<String, Window>, skipped_windows: &[String], clargs: &CmdLineArgs) -> Result<bool, CheckError> {
+    let mut expected_win_vars: Vec<String> = windows.keys()
+        .filter(|win| !clargs.allowed_missing_windows.contains(win))
+        .map(|win| format!("vsw_ada_x{}", win))
+        .filter(|varname| !clargs.exclude_vars.contains(varname))
+        .collect();
     expected_win_vars.sort_unstable();
     let mut unexpected_win_vars: Vec<String> = skipped_windows.iter().map(|win| format!("vsw_ada_x{}", win)).collect();
     unexpected_win_vars.sort_unstable();
 
     if clargs.verbosity > 1 {
-        println!("\n=== Checking windows present ===");
+        _print_header("\n=== Checking windows present ===", clargs);
     }
 
-    let ok_expected = check_variables_present(nch, &expected_win_vars, true, clargs)?;
-    let ok_unexpected = check_variables_present(nch, &unexpected_win_vars, false, clargs)?;
+    let ok_expected = check_names_present(file_vars, &expected_win_vars, true, clargs);
+    let ok_unexpected = check_names_present(file_vars, &unexpected_win_vars, false, clargs);
+    let ok_gas_counts = check_gas_window_counts(file_vars, windows, clargs);
 
     if clargs.verbosity == 1 {
         if ok_expected {
-            if !clargs.failures_only{println!("* PASS: All windows expected to be present are")};
+            if !clargs.failures_only{report!("* PASS: All windows expected to be present are")};
         }else{
-            println!("* FAIL: At least one window expected to be present is missing");
+            report!("* FAIL: At least one window expected to be present is missing");
         }
 
         if ok_unexpected {
-            if !clargs.failures_only{println!("* PASS: All windows expected to be removed are")};
+            if !clargs.failures_only{report!("* PASS: All windows expected to be removed are")};
+        }else{
+            report!("* FAIL: At least one window expected to have been removed is present");
+        }
+
+        if ok_gas_counts {
+            if !clargs.failures_only{report!("* PASS: Every gas has one vsw_ada_x variable per window fitting it")};
+        }else{
+            report!("* FAIL: At least one gas has a different number of vsw_ada_x variables than windows fitting it");
+        }
+    }
+
+    Ok(ok_expected && ok_unexpected && ok_gas_counts)
+}
+
+fn check_gas_window_counts(file_vars: &HashSet<String>, windows: &HashMap<String, Window>, clargs: &CmdLineArgs) -> bool {
+    // Catches a gas fit in several windows (e.g. h2o) losing only some of them
+    lazy_static! {
+        static ref RE: Regex = Regex::new(r"^vsw_ada_x([a-z0-9]+)_\d+[a-z]?$").unwrap();
+    }
+
+    // Windows allowed to be missing are left out of both counts, so they pass whether or not they are present
+    let is_allowed = |win_name: &str| clargs.allowed_missing_windows.iter().any(|w| w == win_name) || clargs.exclude_vars.contains(&format!("vsw_ada_x{}", win_name));
+
+    let mut gas_windows: HashMap<&str, Vec<&String>> = HashMap::new();
+    for (win_name, win) in windows.iter() {
+        if is_allowed(win_name) {
+            continue;
+        }
+        gas_windows.entry(win.gas).or_default().push(win_name);
+    }
+
+    let mut n_file: HashMap<&str, usize> = HashMap::new();
+    for varname in file_vars.iter() {
+        if is_allowed(varname.trim_start_matches("vsw_ada_x")) {
+            continue;
+        }
+        if let Some(caps) = RE.captures(varname) {
+            *n_file.entry(caps.get(1).unwrap().as_str()).or_default() += 1;
+        }
+    }
+
+    let mut gases: Vec<&&str> = gas_windows.keys().collect();
+    gases.sort_unstable();
+
+    let mut all_ok = true;
+    for gas in gases {
+        let mut win_names = gas_windows[*gas].clone();
+        win_names.sort_unstable();
+        let n_expected = win_names.len();
+        let n_found = n_file.get(*gas).copied().unwrap_or(0);
+        let missing: Vec<&str> = win_names.iter()
+            .filter(|win| !file_vars.contains(&format!("vsw_ada_x{}", win)))
+            .map(|win| win.trim_start_matches(&format!("{}_", gas)))
+            .collect();
+
+        if n_found == n_expected && missing.is_empty() {
+            if clargs.verbosity >= 2 && !clargs.failures_only {
+                report!("  - PASS: {} has {} vsw_ada_x{}_* variable(s), matching its windows", gas, n_found, gas);
+            }
         }else{
-            println!("* FAIL: At least one window expected to have been removed is present");
+            all_ok = false;
+            if clargs.verbosity >= 2 {
+                report!("  - FAIL: {} has {} vsw_ada_x{}_* variable(s) but is fit in {} window(s)", gas, n_found, gas, n_expected);
+                if clargs.verbosity >= 3 && !missing.is_empty() {
+                    report!("    - missing center(s): {}", missing.join(", "));
+                }
+            }
         }
     }
 
-    Ok(ok_expected && ok_unexpected)
+    return all_ok;
+}
+
+fn check_variables_present(nch: &netcdf::File, variables: &[String], expected: bool, clargs: &CmdLineArgs) -> Result<bool, CheckError> {
+    return Ok(check_names_present(&_get_variable_names(nch), variables, expected, clargs));
 }
 
-fn check_variables_present<'a>(nch: &netcdf::File, variables: &'a[String], expected: bool, clargs: &CmdLineArgs) -> Result<bool, String> {
+fn check_names_present(file_vars: &HashSet<String>, variables: &[String], expected: bool, clargs: &CmdLineArgs) -> bool {
     // Used to check variables added or removed in Phase 2
     let mut vars_ok = true;
     for varname in variables {
-        if let Some(_) = nch.variable(varname) {
+        if file_vars.contains(varname) {
             if expected {
                 if clargs.verbosity >= 2 {
-                    if !clargs.failures_only{ println!("  - PASS: variable '{}' is present as expected", varname) };
+                    if !clargs.failures_only{ report!("  - PASS: variable '{}' is present as expected", varname) };
                 }
             }else{
                 vars_ok = false;
                 if clargs.verbosity >= 2 {
-                    println!("  - FAIL: variable '{}' is present but should not be", varname);
+                    report!("  - FAIL: variable '{}' is present but should not be", varname);
                 }
             }
         }else{
             if expected {
                 vars_ok = false;
                 if clargs.verbosity >= 2 {
-                    println!("  - FAIL: variable '{}' is not present but should be", varname);
+                    report!("  - FAIL: variable '{}' is not present but should be", varname);
                 }
             }else{
                 if clargs.verbosity >= 2 {
-                    if !clargs.failures_only{ println!("  - PASS: variable '{}' is absent as expected", varname) };
+                    if !clargs.failures_only{ report!("  - PASS: variable '{}' is absent as expected", varname) };
                 }
             }
         }
     }
 
-    return Ok(vars_ok);
+    return vars_ok;
 }
 
-fn _check_write_netcdf_hash(nch: &netcdf::File, clargs: &CmdLineArgs) -> Result<bool, String> {
+fn _check_write_netcdf_hash(nch: &netcdf::File, clargs: &CmdLineArgs) -> Result<bool, CheckError> {
     let att_name = "code_version";
     let att_val = _get_string_attribute_value(nch, att_name, clargs)?;
     if att_val == ATT_MISSING_STR {
         if clargs.verbosity >= 2 {
-            println!("  - FAIL: attribute '{}' is not present", att_name);
+            report!("  - FAIL: attribute '{}' is not present", att_name);
         }
         return Ok(false);
     }
@@ -603,158 +1554,4551 @@ fn _check_write_netcdf_hash(nch: &netcdf::File, clargs: &CmdLineArgs) -> Result<
     let hash = if let Some(caps) = RE.captures(&att_val) {
         caps.get(1).unwrap().as_str()
     }else{
-        return Err(format!("Could not get the write_netcdf commit hash from the attribute {}", att_name));
+        return Err(CheckError::Other(format!("Could not get the write_netcdf commit hash from the attribute {}", att_name)));
     };
 
-    let hash_ok = hash == WRITE_NC_HASH;
+    let expected = clargs.release.write_nc_hash;
+    let hash_ok = hash == expected;
     if hash_ok {
         if !clargs.failures_only{
             if clargs.verbosity == 2 {
-                println!("  - PASS: write_netcdf hash in attribute '{}' has the expected value", att_name);
+                report!("  - PASS: write_netcdf hash in attribute '{}' has the expected value", att_name);
             }else if clargs.verbosity == 3 {
-                println!("  - PASS: write_netcdf hash in attribute '{}' has the expected value ('{}')", att_name, WRITE_NC_HASH);
+                report!("  - PASS: write_netcdf hash in attribute '{}' has the expected value ('{}')", att_name, expected);
             }
         }
     }else{
         if clargs.verbosity >= 2 {
-            println!("  - FAIL: write_netcdf hash in attribute '{}' has the wrong value", att_name);
+            report!("  - FAIL: write_netcdf hash in attribute '{}' has the wrong value", att_name);
         }
         if clargs.verbosity == 3 {
-            println!("      (expected = '{}', actual = '{}')", WRITE_NC_HASH, hash);
+            report!("      (expected = '{}', actual = '{}')", expected, hash);
         }
     }
 
     return Ok(hash_ok);
 }
 
-fn check_program_versions(nch: &netcdf::File, clargs: &CmdLineArgs) -> Result<bool, String> {
+fn check_program_versions(nch: &netcdf::File, clargs: &CmdLineArgs) -> Result<bool, CheckError> {
     if clargs.verbosity > 1 {
-        println!("\n=== Checking program versions ===");
+        _print_header("\n=== Checking program versions ===", clargs);
     }
 
-    let gsetup_ok = _check_string_attribute_value(nch, "gsetup_version", GSETUP_VERSION, clargs)?;
-    let gfit_ok = _check_string_attribute_value(nch, "gfit_version", GFIT_VERSION, clargs)?;
-    let collate_ok = _check_string_attribute_value(nch, "collate_results_version", COLLATE_VERSION, clargs)?;
-    let airmass_ok = _check_string_attribute_value(nch, "apply_airmass_correction_version", AIRMASS_VERSION, clargs)?;
-    let average_ok = _check_string_attribute_value(nch, "average_results_version", AVERAGE_VERSION, clargs)?;
-    let insitu_ok = _check_string_attribute_value(nch, "apply_insitu_correction_version", INSITU_VERSION, clargs)?;
+    let gsetup_ok = _check_string_attribute_value(nch, "gsetup_version", clargs.release.gsetup_version, clargs)?;
+    let gfit_ok = _check_string_attribute_value(nch, "gfit_version", clargs.release.gfit_version, clargs)?;
+    let collate_ok = _check_string_attribute_value(nch, "collate_results_version", clargs.release.collate_version, clargs)?;
+    let airmass_ok = _check_string_attribute_value(nch, "apply_airmass_correction_version", clargs.release.airmass_version, clargs)?;
+    let average_ok = _check_string_attribute_value(nch, "average_results_version", clargs.release.average_version, clargs)?;
+    let insitu_ok = _check_string_attribute_value(nch, "apply_insitu_correction_version", clargs.release.insitu_version, clargs)?;
     let write_nc_ok = _check_write_netcdf_hash(nch, clargs)?;
 
     let all_ok = gsetup_ok && gfit_ok && collate_ok && airmass_ok && average_ok && insitu_ok && write_nc_ok;
 
     if clargs.verbosity == 1 {
         if all_ok && !clargs.failures_only {
-            println!("* PASS: All program versions match expected");
+            report!("* PASS: All program versions match expected");
         }else if !all_ok {
-            println!("* FAIL: At least one program version does not match expected");
+            report!("* FAIL: At least one program version does not match expected");
         }
     }
 
     Ok(all_ok)
 }
 
-fn check_ingaas_variables(nch: &netcdf::File, clargs: &CmdLineArgs) -> Result<bool, String> {
-    let variable_list: Vec<&str> = EXPECTED_INGAAS_VARS.split(',').collect();
-    let ntotal = variable_list.len();
-    let mut nmissing = 0;
+// If at least this fraction of a window's variables are missing, they are reported as one block
+const INGAAS_BLOCK_MISSING_FRACTION: f32 = 0.5;
+
+fn _window_block(varname: &str) -> Option<&str> {
+    // Window variables start with <gas>_<center>, plus a letter for repeated windows
+    lazy_static! {
+        static ref RE: Regex = Regex::new(r"^([a-z0-9]+_\d{4,5}[a-z]?)_").unwrap();
+    }
+    RE.captures(varname).map(|caps| caps.get(1).unwrap().as_str())
+}
+
+fn check_channel_fringe(file_vars: &HashSet<String>, windows: &HashMap<String, Window>, clargs: &CmdLineArgs) -> Result<bool, CheckError> {
+    let mut win_names: Vec<&str> = windows.keys().map(|x| x.as_ref()).collect();
+    win_names.sort_unstable();
 
     if clargs.verbosity > 1 {
-        println!("\n=== Checking InGaAs variables ===");
+        _print_header("\n=== Checking channel fringe variables ===", clargs);
     }
 
-    for varname in variable_list {
-        if let None = nch.variable(varname) {
-            nmissing += 1;
-            if clargs.verbosity >= 3 {
-                if clargs.verbosity == 4 || nmissing < 11 {
-                    println!("    - FAIL: variable is {} missing", varname);
-                }else if nmissing == 11 {
-                    println!("    (further missing variables omitted)");
+    // Windows with the nv or cf flag fit channel fringes, so should have all of the
+    // fringe parameters in the file, and windows without should have none of them
+    let mut all_ok = true;
+    for win in win_names {
+        let fits_cf = windows[win].params.iter().any(|&p| p == "nv" || p == "cf");
+        let cf_vars: Vec<String> = ["cfampocl", "cfperiod", "cfphase"].iter().map(|v| format!("{}_{}", win, v)).collect();
+        let (present, missing): (Vec<&String>, Vec<&String>) = cf_vars.iter().partition(|v| file_vars.contains(*v));
+
+        let win_ok = if fits_cf {missing.is_empty()} else {present.is_empty()};
+        if clargs.verbosity >= 2 {
+            if win_ok {
+                if !clargs.failures_only {
+                    let state = if fits_cf {"has all"} else {"has none"};
+                    report!("  - PASS: {} {} of the channel fringe variables, as expected", win, state);
                 }
+            }else if fits_cf {
+                report!("  - FAIL: {} fits channel fringes (nv/cf) but is missing {}", win, missing.iter().map(|v| v.as_str()).collect::<Vec<_>>().join(", "));
+            }else{
+                report!("  - FAIL: {} does not fit channel fringes but has {}", win, present.iter().map(|v| v.as_str()).collect::<Vec<_>>().join(", "));
             }
         }
+        all_ok = all_ok && win_ok;
     }
 
-    if clargs.verbosity >= 1 {
-        if nmissing == 0 && !clargs.failures_only {
-            println!("* PASS: All expected InGaAs variables present");
-        }else if nmissing > 0 {
-            println!("* FAIL: {}/{} expected InGaAs variables missing", nmissing, ntotal);
+    if clargs.verbosity == 1 {
+        if all_ok {
+            if !clargs.failures_only{ report!("* PASS: Channel fringe variables are present for exactly the windows that fit them") };
+        }else{
+            report!("* FAIL: Channel fringe variables are missing for windows that fit them, or present for windows that do not");
+        }
+    }
+
+    Ok(all_ok)
+}
+
+fn check_zero_offset(nch: &netcdf::File, windows: &HashMap<String, Window>, clargs: &CmdLineArgs) -> Result<bool, CheckError> {
+    let mut win_names: Vec<&str> = windows.keys().map(|x| x.as_ref()).collect();
+    win_names.sort_unstable();
+
+    if clargs.verbosity > 1 {
+        _print_header("\n=== Checking zero-level offsets ===", clargs);
+    }
+
+    // Only windows with the zo flag fit a zero-level offset, which must then be a small fraction of the signal
+    let bounds = format!("[-{}, {}]", clargs.zo_max, clargs.zo_max);
+    let mut all_ok = true;
+    for win in win_names {
+        let fits_zo = windows[win].params.contains(&"zo");
+        let varname = format!("{}_zo", win);
+        let win_ok = match (fits_zo, nch.variable(&varname)) {
+            (true, Some(var)) => {
+                let (n_total, n_wrong) = _count_out_of_range(&var, Some(-clargs.zo_max), Some(clargs.zo_max), clargs)?;
+                _print_bounded_results(&varname, n_total, n_wrong, &bounds, clargs)
+            },
+            (true, None) => {
+                if clargs.verbosity >= 2 {
+                    report!("  - FAIL: {} fits a zero-level offset (zo) but variable '{}' is not present", win, varname);
+                }
+                false
+            },
+            (false, Some(_)) => {
+                if clargs.verbosity >= 2 {
+                    report!("  - FAIL: {} does not fit a zero-level offset but variable '{}' is present", win, varname);
+                }
+                false
+            },
+            (false, None) => true
+        };
+        all_ok = all_ok && win_ok;
+    }
+
+    if clargs.verbosity == 1 {
+        if all_ok {
+            if !clargs.failures_only{ report!("* PASS: Zero-level offsets are present and within {} for exactly the windows that fit them", bounds) };
+        }else{
+            report!("* FAIL: Zero-level offsets are missing, out of bounds, or present for windows that do not fit them");
         }
     }
 
-    Ok(nmissing == 0)
+    Ok(all_ok)
 }
 
+fn check_solar_gas_stretch(nch: &netcdf::File, windows: &HashMap<String, Window>, clargs: &CmdLineArgs) -> Result<bool, CheckError> {
+    let mut win_names: Vec<&str> = windows.keys().map(|x| x.as_ref()).collect();
+    win_names.sort_unstable();
 
-fn driver(nc_file: &str, clargs: &CmdLineArgs) -> Result<bool, String> {
-    
-    let adcfs = read_adcf_table();
-    let aicfs = read_aicf_table();
-    let (windows, skipped_windows) = read_windows_table();
+    if clargs.verbosity > 1 {
+        _print_header("\n=== Checking solar-gas stretches ===", clargs);
+    }
 
-    let nch = match netcdf::open(nc_file) {
-        Ok(h) => h,
-        Err(err) => return Err(format!("Unable to open {}: {}", nc_file, err))
-    };
+    // Only windows with the sg flag fit a solar-gas stretch; the others are not checked
+    let (min, max) = (clargs.sg_expected - clargs.sg_tolerance, clargs.sg_expected + clargs.sg_tolerance);
+    let bounds = format!("[{}, {}]", min, max);
+    let mut all_ok = true;
+    let mut worst: Option<(String, usize, usize)> = None;
+    let mut n_sg_windows = 0;
+    for win in win_names {
+        if !windows[win].params.contains(&"sg") {
+            continue;
+        }
+        n_sg_windows += 1;
+        let varname = format!("{}_sg", win);
+        let win_ok = match nch.variable(&varname) {
+            Some(var) => {
+                let (n_total, n_wrong) = _count_out_of_range(&var, Some(min), Some(max), clargs)?;
+                _update_worst_window(&mut worst, win, n_total, n_wrong);
+                _print_bounded_results(&varname, n_total, n_wrong, &bounds, clargs)
+            },
+            None => {
+                if clargs.verbosity >= 2 {
+                    report!("  - FAIL: {} fits a solar-gas stretch (sg) but variable '{}' is not present", win, varname);
+                }
+                false
+            }
+        };
+        all_ok = all_ok && win_ok;
+    }
 
-    let adcfs_ok = check_adcfs(&nch, &adcfs, clargs)?;
-    let aicfs_ok = check_aicfs(&nch, &aicfs, clargs)?;
-    let sfs_ok = check_window_scale_factors(&nch, &windows, clargs)?;
-    let windows_ok = check_included_windows(&nch, &windows, &skipped_windows, clargs)?;
-    let versions_ok = check_program_versions(&nch, clargs)?;
-    let ingaas_ok = check_ingaas_variables(&nch, clargs)?;
+    let results = WindowFamilyResults{all_ok: all_ok, n_windows: n_sg_windows, bounds: bounds, worst: worst};
+    _print_window_family_summary("Solar-gas stretches (sg)", "sg", &results, clargs);
 
-    let overall_ok = adcfs_ok && aicfs_ok && sfs_ok && windows_ok && versions_ok && ingaas_ok;
-    if clargs.verbosity >= 0 {
-        if clargs.verbosity > 0 {println!("");}
+    Ok(results.all_ok && n_sg_windows > 0)
+}
 
-        if overall_ok {
-            println!("{} PASSES all tests - it appears to be a correct Phase 2 file", nc_file);
+fn check_error_pairing(file_vars: &HashSet<String>, clargs: &CmdLineArgs) -> Result<bool, CheckError> {
+    // The Xgas values and the per-window column and ADA-corrected values must have errors
+    lazy_static! {
+        static ref NEEDS_ERROR_RE: Regex = Regex::new(r"^(?:x[a-z0-9]+|vsw_[a-z0-9]+_\d{4,5}[a-z]?|vsw_ada_x[a-z0-9]+_\d{4,5}[a-z]?)$").unwrap();
+    }
+
+    if clargs.verbosity > 1 {
+        _print_header("\n=== Checking error variable pairing ===", clargs);
+    }
+
+    let mut names: Vec<&String> = file_vars.iter().collect();
+    names.sort_unstable();
+
+    let mut n_orphans = 0;
+    let mut n_missing_errors = 0;
+    for name in names {
+        if let Some(base) = name.strip_suffix("_error") {
+            if !file_vars.contains(base) {
+                n_orphans += 1;
+                if clargs.verbosity >= 2 {
+                    report!("  - FAIL: variable '{}' is present but '{}' is not", name, base);
+                }
+            }
+        }else if NEEDS_ERROR_RE.is_match(name) && !file_vars.contains(&format!("{}_error", name)) {
+            n_missing_errors += 1;
+            if clargs.verbosity >= 2 {
+                report!("  - FAIL: variable '{}' is present but '{}_error' is not", name, name);
+            }
+        }
+    }
+
+    let all_ok = n_orphans == 0 && n_missing_errors == 0;
+    if clargs.verbosity >= 2 && all_ok && !clargs.failures_only {
+        report!("  - PASS: all error variables are paired with their values");
+    }else if clargs.verbosity == 1 {
+        if all_ok {
+            if !clargs.failures_only{ report!("* PASS: All error variables are paired with their values") };
         }else{
-            println!("{} FAILS at least one test - it may be a Phase 1 file or there was a problem in processing.", nc_file);
+            report!("* FAIL: {} error variables have no value and {} values are missing their error", n_orphans, n_missing_errors);
         }
     }
-    
-    return Ok(overall_ok);
+
+    Ok(all_ok)
 }
 
-#[derive(Debug)]
-struct CmdLineArgs {
-    nc_file: String,
-    verbosity: i8,
-    failures_only: bool
+fn _var_in_window(varname: &str, window: &str) -> bool {
+    // Window variables are <window>_..., x<window>_... (ADCFs), or vsw_[ada_x|sf_]<window>[_error]
+    for prefix in ["vsw_ada_x", "vsw_sf_", "vsw_", "x", ""].iter() {
+        if let Some(rest) = varname.strip_prefix(prefix) {
+            if rest == window || rest.starts_with(&format!("{}_", window)) {
+                return true;
+            }
+        }
+    }
+    return false;
 }
 
-fn parse_clargs() -> CmdLineArgs {
-    let yml = clap::load_yaml!("clargs.yml");
-    let clargs = clap::App::from_yaml(yml).version(clap::crate_version!()).get_matches();
+fn _allowed_missing(varname: &str, clargs: &CmdLineArgs) -> bool {
+    clargs.allowed_missing_windows.iter().any(|win| _var_in_window(varname, win))
+}
 
-    let nc_file = clargs.value_of("nc_file").unwrap();
-    let nverb = clargs.occurrences_of("verbose");
-    let nquiet = clargs.occurrences_of("quiet");
-    let failures_only = clargs.occurrences_of("failures_only") > 0;
+fn check_o2_airmass_window(file_vars: &HashSet<String>, clargs: &CmdLineArgs) -> bool {
+    // O2 is the denominator of every Xgas, so without this window none of them are usable.
+    // It is checked on its own so that this stands out from other missing windows.
+    let o2_window = "o2_7885";
+    let mut o2_vars: Vec<&str> = EXPECTED_INGAAS_VARS.split(',').filter(|v| _var_in_window(v, o2_window)).collect();
+    if !o2_vars.contains(&"vsw_ada_xo2_7885") {
+        o2_vars.push("vsw_ada_xo2_7885");
+    }
+    let missing: Vec<&str> = o2_vars.iter().copied().filter(|v| !file_vars.contains(*v)).collect();
 
-    let args = CmdLineArgs{
-        nc_file: String::from(nc_file),
-        verbosity: if nquiet > 0 {-1} else {nverb as i8},
-        failures_only: failures_only
+    if clargs.verbosity > 1 {
+        _print_header("\n=== Checking O2 airmass window ===", clargs);
+    }
+
+    let all_ok = missing.is_empty();
+    if clargs.verbosity >= 2 {
+        if all_ok {
+            if !clargs.failures_only{ report!("  - PASS: all {} {} variables are present", o2_vars.len(), o2_window) };
+        }else{
+            report!("  - FAIL: missing O2 airmass window - all xgas products are invalid ({}/{} {} variables missing)", missing.len(), o2_vars.len(), o2_window);
+            if clargs.verbosity >= 3 {
+                for varname in missing.iter() {
+                    report!("    - {}", varname);
+                }
+            }
+        }
+    }else if clargs.verbosity == 1 {
+        if all_ok {
+            if !clargs.failures_only{ report!("* PASS: The O2 {} airmass window is present", o2_window) };
+        }else{
+            report!("* FAIL: missing O2 airmass window - all xgas products are invalid");
+        }
+    }
+
+    return all_ok;
+}
+
+fn read_expected_vars_file(vars_file: &str) -> Result<Vec<String>, CheckError> {
+    // Variable names separated by commas (like EXPECTED_INGAAS_VARS) and/or whitespace
+    let contents = match std::fs::read_to_string(vars_file) {
+        Ok(s) => s,
+        Err(err) => return Err(CheckError::FileRead(String::from(vars_file), err.to_string()))
     };
+    Ok(contents.split(|c: char| c == ',' || c.is_whitespace()).filter(|v| !v.is_empty()).map(String::from).collect())
+}
 
-    return args;
+fn _filter_expected_vars<'a>(variables: impl Iterator<Item = &'a str>, file_vars: &HashSet<String>, clargs: &CmdLineArgs) -> Vec<&'a str> {
+    variables
+        .filter(|&varname| !clargs.exclude_vars.iter().any(|v| v == varname))
+        .filter(|&varname| !_allowed_missing(varname, clargs) || file_vars.contains(varname))
+        .collect()
 }
 
-fn main() {
-    let clargs = parse_clargs();
+fn check_ingaas_variables(file_vars: &HashSet<String>, clargs: &CmdLineArgs) -> Result<bool, CheckError> {
+    let variable_list = _filter_expected_vars(EXPECTED_INGAAS_VARS.split(','), file_vars, clargs);
 
-    match driver(&clargs.nc_file, &clargs) {
-        Ok(passes) => {
-            if passes {std::process::exit(0);}
-            else {std::process::exit(1);}
-        },
-        Err(msg) => {
-            eprintln!("ERROR: {}", msg);
-            std::process::exit(2);
+    if clargs.verbosity > 1 {
+        _print_header("\n=== Checking InGaAs variables ===", clargs);
+    }
+
+    let alt_file = match &clargs.expected_vars_alt {
+        Some(f) => f,
+        None => return Ok(_check_variable_list(file_vars, &variable_list, "expected InGaAs variables", clargs))
+    };
+
+    // During a transition between variable lists, the file may match either (--match-mode any) or must match both (all).
+    // Only the lists that decide the result are reported.
+    let alt_vars = read_expected_vars_file(alt_file)?;
+    let alt_list = _filter_expected_vars(alt_vars.iter().map(|v| v.as_str()), file_vars, clargs);
+    let default_ok = variable_list.iter().all(|v| file_vars.contains(*v));
+    let alt_ok = alt_list.iter().all(|v| file_vars.contains(*v));
+
+    let all_ok = if clargs.match_mode == "all" {
+        let default_ok = _check_variable_list(file_vars, &variable_list, "expected InGaAs variables", clargs);
+        let alt_ok = _check_variable_list(file_vars, &alt_list, "alternate expected variables", clargs);
+        default_ok && alt_ok
+    }else if default_ok || !alt_ok {
+        let default_ok = _check_variable_list(file_vars, &variable_list, "expected InGaAs variables", clargs);
+        if !alt_ok {
+            _check_variable_list(file_vars, &alt_list, "alternate expected variables", clargs);
         }
+        default_ok
+    }else{
+        _check_variable_list(file_vars, &alt_list, "alternate expected variables", clargs)
+    };
+
+    if clargs.verbosity >= 1 {
+        let matched = match (default_ok, alt_ok) {
+            (true, true) => String::from("both the built in and alternate variable lists"),
+            (true, false) => String::from("only the built in variable list"),
+            (false, true) => format!("only the alternate variable list ({})", alt_file),
+            (false, false) => String::from("neither the built in nor the alternate variable list")
+        };
+        report!("  NOTE: the file matches {}", matched);
+    }
+
+    return Ok(all_ok);
+}
+
+fn _check_variable_list(file_vars: &HashSet<String>, variable_list: &[&str], description: &str, clargs: &CmdLineArgs) -> bool {
+    let ntotal = variable_list.len();
+    let mut nmissing = 0;
+
+    // Most variables belong to a window (e.g. co2_6220_nit), so a Phase 1 file missing a
+    // window is missing dozens of them. Count by window so those can be reported as one block.
+    let mut block_sizes: HashMap<&str, usize> = HashMap::new();
+    let mut block_nmissing: HashMap<&str, usize> = HashMap::new();
+    let mut missing_vars: Vec<&str> = Vec::new();
+    for &varname in variable_list.iter() {
+        let block = _window_block(varname);
+        if let Some(b) = block {
+            *block_sizes.entry(b).or_insert(0) += 1;
+        }
+
+        if !file_vars.contains(varname) {
+            nmissing += 1;
+            missing_vars.push(varname);
+            if let Some(b) = block {
+                *block_nmissing.entry(b).or_insert(0) += 1;
+            }
+        }
+    }
+
+    let mut reported_blocks: HashSet<&str> = HashSet::new();
+    let mut nlines = 0;
+    for varname in missing_vars {
+        let collapsed_block = _window_block(varname).filter(|b| {
+            block_nmissing[b] as f32 / block_sizes[b] as f32 >= INGAAS_BLOCK_MISSING_FRACTION
+        });
+
+        if let Some(block) = collapsed_block {
+            if !reported_blocks.insert(block) {
+                continue;
+            }
+            if clargs.verbosity >= 2 {
+                let (nblock_missing, nblock) = (block_nmissing[block], block_sizes[block]);
+                if nblock_missing == nblock {
+                    report!("  - FAIL: entire {} window block missing ({} variables)", block, nblock);
+                }else{
+                    report!("  - FAIL: {}/{} variables of the {} window block missing", nblock_missing, nblock, block);
+                }
+            }
+        }else if clargs.verbosity >= 3 {
+            nlines += 1;
+            if clargs.verbosity == 4 || nlines < 11 {
+                report!("    - FAIL: variable is {} missing", varname);
+            }else if nlines == 11 {
+                report!("    (further missing variables omitted)");
+            }
+        }
+    }
+
+    if clargs.verbosity >= 1 {
+        if nmissing == 0 && !clargs.failures_only {
+            report!("* PASS: All {} present", description);
+        }else if nmissing > 0 {
+            report!("* FAIL: {}/{} {} missing", nmissing, ntotal, description);
+        }
+    }
+
+    return nmissing == 0;
+}
+
+
+// *************** //
+// OPTIONAL CHECKS //
+// *************** //
+
+// Variables added to the priors in Phase 2; a file without them is very likely Phase 1
+const PHASE2_PRIOR_VARS: [&'static str; 2] = ["prior_effective_latitude", "prior_mid_tropospheric_potential_temperature"];
+
+fn check_priors(nch: &netcdf::File, clargs: &CmdLineArgs) -> Result<bool, CheckError> {
+    if clargs.verbosity > 1 {
+        _print_header("\n=== Checking priors ===", clargs);
+    }
+
+    let lats_ok = check_prior_latitudes(nch, clargs)?;
+    let files_ok = check_prior_files(nch, clargs)?;
+    let pairs_ok = check_prior_cell_pairing(nch, clargs);
+    let theta_ok = check_prior_theta(nch, clargs)?;
+    let tropopause_ok = check_prior_tropopause(nch, clargs)?;
+    let index_ok = check_prior_index(nch, clargs)?;
+    let density_ok = check_prior_density_closure(nch, clargs)?;
+
+    let missing_phase2_vars: Vec<&str> = PHASE2_PRIOR_VARS.iter().copied().filter(|v| nch.variable(v).is_none()).collect();
+    if !missing_phase2_vars.is_empty() && clargs.verbosity >= 1 {
+        report!("  NOTE: {} missing; this strongly suggests a Phase 1 file", missing_phase2_vars.join(" and "));
+    }
+
+    Ok(lats_ok && files_ok && pairs_ok && theta_ok && tropopause_ok && index_ok && density_ok)
+}
+
+// GGG2020 priors are every 3 hours, so the one used for an observation should never be further away than this
+const PRIOR_TIME_MAX_OFFSET_HOURS: f64 = 3.0;
+
+fn _get_seconds_since_1970(var: &netcdf::Variable, clargs: &CmdLineArgs) -> Result<Option<Vec<f64>>, CheckError> {
+    // None if the units are missing or not CF-style time units
+    let (unit_seconds, epoch) = match _get_units(var).as_deref().and_then(_parse_time_units) {
+        Some(v) => v,
+        None => return Ok(None)
+    };
+    let offset = (epoch - _from_seconds_since_1970(0.0)).num_milliseconds() as f64 / 1000.0;
+    _check_memory_budget(var, &_full_shape(var), std::mem::size_of::<f64>(), clargs)?;
+    let values = match var.values::<f64>(None, None) {
+        Ok(arr) => arr,
+        Err(err) => return Err(CheckError::VariableRead(var.name(), err.to_string()))
+    };
+    return Ok(Some(values.iter().map(|&t| t * unit_seconds + offset).collect()));
+}
+
+fn _from_seconds_since_1970(seconds: f64) -> NaiveDateTime {
+    NaiveDate::from_ymd_opt(1970, 1, 1).unwrap().and_hms_opt(0, 0, 0).unwrap() + chrono::Duration::milliseconds((seconds * 1000.0) as i64)
+}
+
+fn check_time_range(nch: &netcdf::File, clargs: &CmdLineArgs) -> Result<bool, CheckError> {
+    if clargs.verbosity > 1 {
+        _print_header("\n=== Checking time range ===", clargs);
+    }
+
+    // Wrong time units or epoch usually still give numbers, just ones decades away from the real dates
+    let time_var = match nch.variable("time") {
+        Some(v) => v,
+        None => {
+            if clargs.verbosity >= 1 {
+                report!("* FAIL: time is missing");
+            }
+            return Ok(false);
+        }
+    };
+    let times = match _get_seconds_since_1970(&time_var, clargs)? {
+        Some(t) => t,
+        None => {
+            if clargs.verbosity >= 1 {
+                report!("* FAIL: time does not have CF-style units (e.g. \"seconds since 1970-01-01 00:00:00\"), so the dates are unknown");
+            }
+            return Ok(false);
+        }
+    };
+    let fill = time_var.fill_value::<f64>().ok().flatten();
+    let times: Vec<f64> = times.into_iter().filter(|t| !t.is_nan() && Some(*t) != fill).collect();
+
+    let earliest = (clargs.earliest_time - _from_seconds_since_1970(0.0)).num_milliseconds() as f64 / 1000.0;
+    let now = match std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH) {
+        Ok(d) => d.as_secs_f64(),
+        Err(err) => return Err(CheckError::Other(format!("Could not get the current time: {}", err)))
+    };
+    let n_early = times.iter().filter(|&&t| t < earliest).count();
+    let n_future = times.iter().filter(|&&t| t > now).count();
+    let first = times.iter().copied().fold(f64::INFINITY, f64::min);
+    let last = times.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    let span = if times.is_empty() {
+        String::from("no valid times")
+    }else{
+        format!("{} to {}", _from_seconds_since_1970(first).format("%Y-%m-%d %H:%M:%S"), _from_seconds_since_1970(last).format("%Y-%m-%d %H:%M:%S"))
+    };
+
+    let all_ok = n_early == 0 && n_future == 0;
+    if clargs.verbosity >= 2 {
+        if n_early > 0 {
+            report!("  - FAIL: {}/{} records are before {} (records span {})", n_early, times.len(), clargs.earliest_time, span);
+        }
+        if n_future > 0 {
+            report!("  - FAIL: {}/{} records are in the future (records span {})", n_future, times.len(), span);
+        }
+        if all_ok && !clargs.failures_only {
+            report!("  - PASS: all {} records are between {} and now (records span {})", times.len(), clargs.earliest_time, span);
+        }
+    }else if clargs.verbosity == 1 {
+        if all_ok {
+            if !clargs.failures_only{ report!("* PASS: All record times are between {} and now", clargs.earliest_time) };
+        }else{
+            report!("* FAIL: Record times span {}, outside {} to now; the time units or epoch may be wrong", span, clargs.earliest_time);
+        }
+    }
+
+    Ok(all_ok)
+}
+
+fn check_prior_index(nch: &netcdf::File, clargs: &CmdLineArgs) -> Result<bool, CheckError> {
+    let mut vars = Vec::new();
+    for varname in ["prior_index", "time", "prior_time"].iter() {
+        match nch.variable(varname) {
+            Some(v) => vars.push(v),
+            None => {
+                if clargs.verbosity >= 2 {
+                    report!("  - FAIL: variable '{}' is not present but should be", varname);
+                }
+            }
+        }
+    }
+    if vars.len() < 3 {
+        if clargs.verbosity == 1 {
+            report!("* FAIL: prior_index, time, and/or prior_time are missing");
+        }
+        return Ok(false);
+    }
+
+    _check_memory_budget(&vars[0], &_full_shape(&vars[0]), std::mem::size_of::<f64>(), clargs)?;
+    let prior_index = match vars[0].values::<f64>(None, None) {
+        Ok(arr) => arr.iter().copied().collect::<Vec<f64>>(),
+        Err(err) => return Err(CheckError::VariableRead(String::from("prior_index"), err.to_string()))
+    };
+    let (times, prior_times) = match (_get_seconds_since_1970(&vars[1], clargs)?, _get_seconds_since_1970(&vars[2], clargs)?) {
+        (Some(t), Some(p)) => (t, p),
+        _ => return Err(CheckError::Other(String::from("time and/or prior_time do not have CF-style time units, so cannot be compared")))
+    };
+    if prior_index.len() != times.len() {
+        if clargs.verbosity >= 1 {
+            report!("* FAIL: prior_index has {} values, but time has {}", prior_index.len(), times.len());
+        }
+        return Ok(false);
+    }
+
+    let max_offset = PRIOR_TIME_MAX_OFFSET_HOURS * 3600.0;
+    let mut n_invalid = 0;
+    let mut n_far = 0;
+    for (i, (&idx, &t)) in prior_index.iter().zip(times.iter()).enumerate() {
+        if idx < 0.0 || idx.fract() != 0.0 || idx as usize >= prior_times.len() {
+            n_invalid += 1;
+            if clargs.verbosity >= 3 && n_invalid <= 10 {
+                report!("    - record {}: prior_index {} is not an index into the {} prior_times", i, idx, prior_times.len());
+            }
+        }else if (prior_times[idx as usize] - t).abs() > max_offset {
+            n_far += 1;
+            if clargs.verbosity >= 3 && n_far <= 10 {
+                report!("    - record {}: prior_time is {:.1} h from time", i, (prior_times[idx as usize] - t) / 3600.0);
+            }
+        }
+    }
+
+    let n_total = prior_index.len();
+    let index_ok = _print_bounded_results("prior_index", n_total, n_invalid, &format!("[0, {})", prior_times.len()), clargs);
+    let time_ok = n_far == 0;
+    if clargs.verbosity >= 2 {
+        if time_ok {
+            if !clargs.failures_only{ report!("  - PASS: every valid prior_index points to a prior_time within {} h of time", PRIOR_TIME_MAX_OFFSET_HOURS) };
+        }else{
+            report!("  - FAIL: {}/{} ({}) records have a prior_time more than {} h from time", n_far, n_total, _format_percent(n_far, n_total), PRIOR_TIME_MAX_OFFSET_HOURS);
+        }
+    }
+    let all_ok = index_ok && time_ok;
+
+    if clargs.verbosity == 1 {
+        if all_ok {
+            if !clargs.failures_only{ report!("* PASS: prior_index points every record to a prior within {} h of it", PRIOR_TIME_MAX_OFFSET_HOURS) };
+        }else{
+            report!("* FAIL: prior_index has {} invalid indices and {} records whose prior is more than {} h away", n_invalid, n_far, PRIOR_TIME_MAX_OFFSET_HOURS);
+        }
+    }
+
+    Ok(all_ok)
+}
+
+// Boltzmann constant, J/K
+const BOLTZMANN: f64 = 1.380649e-23;
+
+fn check_prior_density_closure(nch: &netcdf::File, clargs: &CmdLineArgs) -> Result<bool, CheckError> {
+    // The species priors are mole fractions rather than number densities, so prior_density is closed
+    // against the ideal gas law from prior_pressure and prior_temperature instead of a sum of species.
+    let vars: Vec<Result<netcdf::Variable, CheckError>> = ["prior_density", "prior_pressure", "prior_temperature"].iter().map(|v| _get_var(nch, v)).collect();
+    let (dens_var, pres_var, temp_var) = match (&vars[0], &vars[1], &vars[2]) {
+        (Ok(d), Ok(p), Ok(t)) => (d, p, t),
+        _ => {
+            if clargs.verbosity >= 2 {
+                report!("  - FAIL: prior_density, prior_pressure, and/or prior_temperature are missing");
+            }else if clargs.verbosity == 1 {
+                report!("* FAIL: prior_density, prior_pressure, and/or prior_temperature are missing");
+            }
+            return Ok(false);
+        }
+    };
+
+    // GGG writes pressure in atm and density in molecules/cm3, but allow SI units for either
+    let pres_scale = match _get_units(pres_var).as_deref() {
+        Some("Pa") => 1.0,
+        Some("hPa") | Some("mbar") => 100.0,
+        _ => 101325.0
+    };
+    let m3_per_dens_unit = match _get_units(dens_var).as_deref() {
+        Some("m-3") | Some("m^-3") | Some("molecules.m-3") | Some("molecules/m3") => 1.0,
+        _ => 1e-6
+    };
+
+    // --first-n and --since keep whole profiles, so the level is still the index within the last dimension
+    let n_levels = dens_var.dimensions().last().map(|d| d.len()).unwrap_or(1).max(1);
+    let dens = _get_record_data(dens_var, clargs)?;
+    let pres = _get_record_data(pres_var, clargs)?;
+    let temp = _get_record_data(temp_var, clargs)?;
+    _check_same_length("prior_density", dens.len(), "prior_pressure", pres.len())?;
+    _check_same_length("prior_density", dens.len(), "prior_temperature", temp.len())?;
+    let fills = [_get_fill_value(dens_var), _get_fill_value(pres_var), _get_fill_value(temp_var)];
+
+    let tol = clargs.density_closure_tolerance as f64;
+    let mut n_valid: usize = 0;
+    let mut level_failures = vec![0usize; n_levels];
+    for i in 0..dens.len() {
+        if _is_fill_or_nan(dens[i], fills[0]) || _is_fill_or_nan(pres[i], fills[1]) || _is_fill_or_nan(temp[i], fills[2]) {
+            continue;
+        }
+        n_valid += 1;
+        let expected = pres[i] as f64 * pres_scale / (BOLTZMANN * temp[i] as f64) * m3_per_dens_unit;
+        // A zero or non-finite P/kT makes this NaN or infinite, which must not pass
+        let rel_diff = ((dens[i] as f64 - expected) / expected).abs();
+        if !rel_diff.is_finite() || rel_diff > tol {
+            level_failures[i % n_levels] += 1;
+        }
+    }
+
+    let n_wrong: usize = level_failures.iter().sum();
+    let all_ok = _print_bounded_results("prior_density", n_valid, n_wrong, &format!("P/kT within {}%", tol * 100.0), clargs);
+    if !all_ok && clargs.verbosity == 3 {
+        let bad_levels: Vec<String> = level_failures.iter().enumerate().filter(|(_, &n)| n > 0).map(|(lev, n)| format!("{} ({})", lev, n)).collect();
+        report!("    - levels that do not close (number of profiles): {}", bad_levels.join(", "));
+    }
+
+    if clargs.verbosity == 1 {
+        if all_ok {
+            if !clargs.failures_only{ report!("* PASS: prior_density matches prior_pressure and prior_temperature") };
+        }else{
+            report!("* FAIL: prior_density does not match prior_pressure and prior_temperature at {} level(s)", level_failures.iter().filter(|&&n| n > 0).count());
+        }
+    }
+
+    Ok(all_ok)
+}
+
+fn check_prior_tropopause(nch: &netcdf::File, clargs: &CmdLineArgs) -> Result<bool, CheckError> {
+    // Tropopauses outside this band (km) are implausible anywhere on Earth
+    let (min_plausible, max_plausible) = (5.0, 20.0);
+    let tropo_var = _get_var(nch, "prior_tropopause_altitude");
+    let alt_var = _get_var(nch, "prior_altitude");
+    let (tropo_var, alt_var) = match (tropo_var, alt_var) {
+        (Ok(t), Ok(a)) => (t, a),
+        _ => {
+            if clargs.verbosity >= 2 {
+                report!("  - FAIL: prior_tropopause_altitude and/or prior_altitude are missing");
+            }else if clargs.verbosity == 1 {
+                report!("* FAIL: prior_tropopause_altitude and/or prior_altitude are missing");
+            }
+            return Ok(false);
+        }
+    };
+
+    // GGG writes both in km, but allow either to be in meters
+    let km_scale = |var: &netcdf::Variable| if matches!(_get_units(var).as_deref(), Some("m") | Some("meters")) {0.001} else {1.0};
+    let tropo_scale = km_scale(&tropo_var);
+    let alt_scale = km_scale(&alt_var);
+
+    let alt_fill = _get_fill_value(&alt_var);
+    let alts: Vec<f32> = _get_float_data(&alt_var, clargs)?.into_iter()
+        .filter(|&a| !_is_fill_or_nan(a, alt_fill))
+        .map(|a| a * alt_scale)
+        .collect();
+    let grid_min = alts.iter().copied().fold(f32::INFINITY, f32::min);
+    let grid_max = alts.iter().copied().fold(f32::NEG_INFINITY, f32::max);
+
+    let tropo_fill = _get_fill_value(&tropo_var);
+    let tropo: Vec<f32> = _get_record_data(&tropo_var, clargs)?.into_iter()
+        .filter(|&t| !_is_fill_or_nan(t, tropo_fill))
+        .map(|t| t * tropo_scale)
+        .collect();
+    let n_outside_grid = tropo.iter().filter(|&&t| t < grid_min || t > grid_max).count();
+    let n_implausible = tropo.iter().filter(|&&t| t < min_plausible || t > max_plausible).count();
+
+    let grid_ok = _print_bounded_results("prior_tropopause_altitude", tropo.len(), n_outside_grid, &format!("the prior_altitude grid [{}, {}] km", grid_min, grid_max), clargs);
+    let band_ok = _print_bounded_results("prior_tropopause_altitude", tropo.len(), n_implausible, &format!("the plausible range [{}, {}] km", min_plausible, max_plausible), clargs);
+    let all_ok = grid_ok && band_ok;
+
+    if clargs.verbosity == 1 {
+        if all_ok {
+            if !clargs.failures_only{ report!("* PASS: Prior tropopause altitudes are within the prior altitude grid and [{}, {}] km", min_plausible, max_plausible) };
+        }else{
+            report!("* FAIL: Prior tropopause altitudes are outside the prior altitude grid and/or [{}, {}] km", min_plausible, max_plausible);
+        }
+    }
+
+    Ok(all_ok)
+}
+
+fn check_prior_theta(nch: &netcdf::File, clargs: &CmdLineArgs) -> Result<bool, CheckError> {
+    // Mid-tropospheric potential temperatures well outside this range (K) mean the prior was generated wrongly
+    let varname = "prior_mid_tropospheric_potential_temperature";
+    let (min, max) = (250.0, 400.0);
+    let all_ok = match nch.variable(varname) {
+        Some(var) => {
+            let (n_total, n_wrong) = _count_out_of_range(&var, Some(min), Some(max), clargs)?;
+            _print_bounded_results(varname, n_total, n_wrong, &format!("[{}, {}] K", min, max), clargs)
+        },
+        None => {
+            if clargs.verbosity >= 2 {
+                report!("  - FAIL: variable '{}' is not present but should be", varname);
+            }
+            false
+        }
+    };
+
+    if clargs.verbosity == 1 {
+        if all_ok {
+            if !clargs.failures_only{ report!("* PASS: Prior mid-tropospheric potential temperature is present and within [{}, {}] K", min, max) };
+        }else{
+            report!("* FAIL: Prior mid-tropospheric potential temperature is missing or outside [{}, {}] K", min, max);
+        }
+    }
+
+    Ok(all_ok)
+}
+
+fn check_prior_latitudes(nch: &netcdf::File, clargs: &CmdLineArgs) -> Result<bool, CheckError> {
+    let lat_vars = [String::from("prior_effective_latitude"), String::from("prior_equivalent_latitude")];
+    let present_ok = check_variables_present(nch, &lat_vars, true, clargs)?;
+
+    let mut values_ok = true;
+    for varname in lat_vars.iter() {
+        if let Some(var) = nch.variable(varname) {
+            let var_ok = _all_in_range_float(&var, Some(-90.0), Some(90.0), clargs)?;
+            values_ok = values_ok && var_ok;
+
+            if clargs.verbosity == 2 {
+                if var_ok {
+                    if !clargs.failures_only{ report!("  - PASS: {} values are all valid latitudes", varname) };
+                }else{
+                    report!("  - FAIL: {} has values outside [-90, 90]", varname);
+                }
+            }
+        }
+    }
+
+    let all_ok = present_ok && values_ok;
+    if clargs.verbosity == 1 {
+        if all_ok {
+            if !clargs.failures_only{ report!("* PASS: Prior effective and equivalent latitudes are present and valid") };
+        }else{
+            report!("* FAIL: Prior effective and/or equivalent latitudes are missing or out of range");
+        }
+    }
+
+    Ok(all_ok)
+}
+
+fn _prior_file_stem(filename: &str) -> Option<&str> {
+    // Both .mod and .vmr files are named e.g. FPIT_2018010100Z_45N_090W.mod; the date,
+    // hour, and location part identifies which profile it belongs to
+    lazy_static! {
+        static ref RE: Regex = Regex::new(r"\d{10}Z_\d+[NS]_\d+[EW]").unwrap();
+    }
+    RE.find(filename).map(|m| m.as_str())
+}
+
+fn check_prior_files(nch: &netcdf::File, clargs: &CmdLineArgs) -> Result<bool, CheckError> {
+    let file_vars = [String::from("prior_modfile"), String::from("prior_vmrfile")];
+    if !check_variables_present(nch, &file_vars, true, clargs)? {
+        if clargs.verbosity == 1 {
+            report!("* FAIL: prior_modfile and/or prior_vmrfile are missing");
+        }
+        return Ok(false);
+    }
+
+    let mod_var = _get_var(nch, "prior_modfile")?;
+    let vmr_var = _get_var(nch, "prior_vmrfile")?;
+    if mod_var.len() != vmr_var.len() {
+        if clargs.verbosity >= 1 {
+            report!("* FAIL: prior_modfile and prior_vmrfile have different lengths ({} vs. {})", mod_var.len(), vmr_var.len());
+        }
+        return Ok(false);
+    }
+
+    let mut n_empty = 0;
+    let mut n_mismatch = 0;
+    for i in 0..mod_var.len() {
+        let modfile = match mod_var.string_value(Some(&[i])) {
+            Ok(v) => v,
+            Err(err) => return Err(CheckError::VariableRead(format!("prior_modfile[{}]", i), err.to_string()))
+        };
+        let vmrfile = match vmr_var.string_value(Some(&[i])) {
+            Ok(v) => v,
+            Err(err) => return Err(CheckError::VariableRead(format!("prior_vmrfile[{}]", i), err.to_string()))
+        };
+
+        if modfile.trim().is_empty() || vmrfile.trim().is_empty() {
+            n_empty += 1;
+            if clargs.verbosity >= 3 {
+                report!("    - prior {} has an empty mod and/or vmr file name", i);
+            }
+        }else if _prior_file_stem(&modfile).is_none() || _prior_file_stem(&modfile) != _prior_file_stem(&vmrfile) {
+            n_mismatch += 1;
+            if clargs.verbosity >= 3 {
+                report!("    - prior {}: '{}' and '{}' are not for the same profile", i, modfile, vmrfile);
+            }
+        }
+    }
+
+    if clargs.verbosity >= 2 {
+        if n_empty > 0 {
+            report!("  - FAIL: {} of {} priors have an empty prior_modfile or prior_vmrfile", n_empty, mod_var.len());
+        }
+        if n_mismatch > 0 {
+            report!("  - FAIL: {} of {} priors have prior_modfile and prior_vmrfile for different profiles", n_mismatch, mod_var.len());
+        }
+        if n_empty == 0 && n_mismatch == 0 && !clargs.failures_only {
+            report!("  - PASS: all {} priors have matching prior_modfile and prior_vmrfile", mod_var.len());
+        }
+    }
+
+    let all_ok = n_empty == 0 && n_mismatch == 0;
+    if clargs.verbosity == 1 {
+        if all_ok {
+            if !clargs.failures_only{ report!("* PASS: Prior mod and vmr files are present and consistent") };
+        }else{
+            report!("* FAIL: Prior mod and/or vmr file names are empty or inconsistent");
+        }
+    }
+
+    Ok(all_ok)
+}
+
+fn read_apriori_checksums(nch: &netcdf::File, clargs: &CmdLineArgs) -> Result<HashSet<(String, String)>, CheckError> {
+    // The (prior_vmrfile, apriori_checksum) pairs used by each record; prior_index maps records to priors
+    let index_var = _get_var(nch, "prior_index")?;
+    let vmr_var = _get_var(nch, "prior_vmrfile")?;
+    let checksum_var = _get_var(nch, "apriori_checksum")?;
+    let prior_index = _get_float_data(&index_var, clargs)?;
+    _check_same_length("prior_index", prior_index.len(), "apriori_checksum", checksum_var.len())?;
+
+    let mut vmrfiles = Vec::with_capacity(vmr_var.len());
+    for i in 0..vmr_var.len() {
+        match vmr_var.string_value(Some(&[i])) {
+            Ok(v) => vmrfiles.push(v),
+            Err(err) => return Err(CheckError::VariableRead(format!("prior_vmrfile[{}]", i), err.to_string()))
+        }
+    }
+
+    let mut pairs = HashSet::new();
+    for (i, &idx) in prior_index.iter().enumerate() {
+        let vmrfile = match vmrfiles.get(idx as usize) {
+            Some(v) if idx >= 0.0 => v,
+            _ => return Err(CheckError::Other(format!("prior_index[{}] = {} is not a valid prior", i, idx)))
+        };
+        let checksum = match checksum_var.string_value(Some(&[i])) {
+            Ok(v) => v,
+            Err(err) => return Err(CheckError::VariableRead(format!("apriori_checksum[{}]", i), err.to_string()))
+        };
+        pairs.insert((vmrfile.trim().to_owned(), checksum.trim().to_owned()));
+    }
+
+    return Ok(pairs);
+}
+
+fn check_checksums(nch: &netcdf::File, clargs: &CmdLineArgs) -> Result<bool, CheckError> {
+    if clargs.verbosity > 1 {
+        _print_header("\n=== Checking checksums ===", clargs);
+    }
+
+    let apriori_ok = check_apriori_checksum_mapping(&read_apriori_checksums(nch, clargs)?, clargs);
+    let windows_ok = check_windows_checksum(nch, clargs)?;
+    Ok(apriori_ok && windows_ok)
+}
+
+fn _windows_table_checksum(release: &Release) -> String {
+    // GGG's checksums are the MD5 of the whole file as it is on disk, as a lowercase hex string.
+    // The embedded table is that file's text without its final newline, so that is added back.
+    format!("{:x}", md5::compute(format!("{}\n", release.windows_table)))
+}
+
+fn check_windows_checksum(nch: &netcdf::File, clargs: &CmdLineArgs) -> Result<bool, CheckError> {
+    let var = match nch.variable("windows_checksum") {
+        Some(v) => v,
+        None => {
+            if clargs.verbosity >= 2 {
+                report!("  - FAIL: variable 'windows_checksum' is not present but should be");
+            }else if clargs.verbosity == 1 {
+                report!("* FAIL: windows_checksum is missing");
+            }
+            return Ok(false);
+        }
+    };
+
+    let expected = _windows_table_checksum(clargs.release);
+    let mut n_wrong = 0;
+    let mut first_wrong = None;
+    for i in 0..var.len() {
+        let checksum = match var.string_value(Some(&[i])) {
+            Ok(v) => v,
+            Err(err) => return Err(CheckError::VariableRead(format!("windows_checksum[{}]", i), err.to_string()))
+        };
+        if checksum.trim() != expected {
+            n_wrong += 1;
+            first_wrong.get_or_insert(checksum);
+        }
+    }
+
+    let all_ok = n_wrong == 0;
+    if clargs.verbosity >= 2 {
+        if all_ok {
+            if !clargs.failures_only{ report!("  - PASS: windows_checksum matches the expected windows table ({})", expected) };
+        }else{
+            report!("  - FAIL: {}/{} windows_checksum values are not {}, the checksum of the expected windows table", n_wrong, var.len(), expected);
+            if clargs.verbosity >= 3 {
+                report!("    - first different value is '{}'", first_wrong.unwrap_or_default().trim());
+            }
+        }
+    }else if clargs.verbosity == 1 {
+        if all_ok {
+            if !clargs.failures_only{ report!("* PASS: windows_checksum matches the expected windows table") };
+        }else{
+            report!("* FAIL: windows_checksum does not match the expected windows table, so the file used a different window list");
+        }
+    }
+
+    Ok(all_ok)
+}
+
+fn check_apriori_checksum_mapping(pairs: &HashSet<(String, String)>, clargs: &CmdLineArgs) -> bool {
+    // The same vmr file must always give the same checksum, and different vmr files different ones.
+    // This takes the pairs from one file, or from every file in a manifest.
+    let mut by_vmr: HashMap<&str, Vec<&str>> = HashMap::new();
+    let mut by_checksum: HashMap<&str, Vec<&str>> = HashMap::new();
+    for (vmrfile, checksum) in pairs.iter() {
+        by_vmr.entry(vmrfile).or_default().push(checksum);
+        by_checksum.entry(checksum).or_default().push(vmrfile);
+    }
+
+    let mut collisions: Vec<(&&str, &Vec<&str>)> = by_vmr.iter().filter(|(_, c)| c.len() > 1).collect();
+    let mut reuses: Vec<(&&str, &Vec<&str>)> = by_checksum.iter().filter(|(_, v)| v.len() > 1).collect();
+    collisions.sort_unstable();
+    reuses.sort_unstable();
+
+    if clargs.verbosity >= 2 {
+        if collisions.is_empty() {
+            if !clargs.failures_only{ report!("  - PASS: each of the {} vmr files has a single apriori_checksum", by_vmr.len()) };
+        }else{
+            report!("  - FAIL: {}/{} vmr files have more than one apriori_checksum", collisions.len(), by_vmr.len());
+        }
+        if clargs.verbosity >= 3 {
+            for (vmrfile, checksums) in collisions.iter() {
+                let mut checksums = (*checksums).clone();
+                checksums.sort_unstable();
+                report!("    - {}: {}", vmrfile, checksums.join(", "));
+            }
+        }
+
+        if reuses.is_empty() {
+            if !clargs.failures_only{ report!("  - PASS: each of the {} apriori_checksums belongs to a single vmr file", by_checksum.len()) };
+        }else{
+            report!("  - FAIL: {}/{} apriori_checksums are shared by different vmr files", reuses.len(), by_checksum.len());
+        }
+        if clargs.verbosity >= 3 {
+            for (checksum, vmrfiles) in reuses.iter() {
+                let mut vmrfiles = (*vmrfiles).clone();
+                vmrfiles.sort_unstable();
+                report!("    - {}: {}", checksum, vmrfiles.join(", "));
+            }
+        }
+    }
+
+    let all_ok = collisions.is_empty() && reuses.is_empty();
+    if clargs.verbosity == 1 {
+        if all_ok {
+            if !clargs.failures_only{ report!("* PASS: apriori_checksum maps one to one with prior_vmrfile") };
+        }else{
+            report!("* FAIL: apriori_checksum does not map one to one with prior_vmrfile ({} vmr files with several checksums, {} checksums shared by several vmr files)", collisions.len(), reuses.len());
+        }
+    }
+
+    return all_ok;
+}
+
+fn check_prior_cell_pairing(nch: &netcdf::File, clargs: &CmdLineArgs) -> bool {
+    // Each prior_<N><species> profile should have a matching cell_<N><species>; collect
+    // the species from the expected list and the file in case the file has extra ones
+    lazy_static! {
+        static ref RE: Regex = Regex::new(r"^(?:prior|cell)_(\d+[a-z][a-z0-9]*)$").unwrap();
+    }
+
+    let file_vars: Vec<String> = nch.variables().map(|var| var.name()).collect();
+    let mut species: Vec<String> = EXPECTED_INGAAS_VARS.split(',')
+        .map(String::from)
+        .chain(file_vars.iter().cloned())
+        .filter_map(|name| RE.captures(&name).map(|caps| caps[1].to_owned()))
+        .collect();
+    species.sort_unstable();
+    species.dedup();
+
+    let mut n_unpaired = 0;
+    for spec in species.iter() {
+        let prior_name = format!("prior_{}", spec);
+        let cell_name = format!("cell_{}", spec);
+        let has_prior = nch.variable(&prior_name).is_some();
+        let has_cell = nch.variable(&cell_name).is_some();
+
+        if has_prior != has_cell {
+            n_unpaired += 1;
+            let (present, absent) = if has_prior { (prior_name, cell_name) } else { (cell_name, prior_name) };
+            if clargs.verbosity >= 2 {
+                report!("  - FAIL: variable '{}' is present but '{}' is not", present, absent);
+            }
+        }
+    }
+
+    let all_ok = n_unpaired == 0;
+    if clargs.verbosity >= 2 && all_ok && !clargs.failures_only {
+        report!("  - PASS: all prior and cell profiles are paired");
+    }else if clargs.verbosity == 1 {
+        if all_ok {
+            if !clargs.failures_only{ report!("* PASS: All prior and cell profiles are paired") };
+        }else{
+            report!("* FAIL: {} prior or cell profiles are missing their counterpart", n_unpaired);
+        }
+    }
+
+    return all_ok;
+}
+
+fn check_laser_sampling(nch: &netcdf::File, clargs: &CmdLineArgs) -> Result<bool, CheckError> {
+    if clargs.verbosity > 1 {
+        _print_header("\n=== Checking laser sampling errors ===", clargs);
+    }
+
+    // These are small fractional corrections, so should be within +/- the threshold
+    let threshold = clargs.lse_threshold;
+    let bounds = format!("[{}, {}]", -threshold, threshold);
+
+    let mut all_ok = true;
+    for varname in ["lse", "lsu"].iter() {
+        let var_ok = match nch.variable(varname) {
+            Some(var) => {
+                let (n_total, n_wrong) = _count_out_of_range(&var, Some(-threshold), Some(threshold), clargs)?;
+                _print_bounded_results(varname, n_total, n_wrong, &bounds, clargs)
+            },
+            None => {
+                if clargs.verbosity >= 2 {
+                    report!("  - FAIL: variable '{}' is not present but should be", varname);
+                }
+                false
+            }
+        };
+        all_ok = all_ok && var_ok;
+    }
+
+    let flags_ok = check_laser_sampling_flags(nch, clargs)?;
+
+    if clargs.verbosity == 1 {
+        if all_ok {
+            if !clargs.failures_only{ report!("* PASS: Laser sampling errors are within {}", bounds) };
+        }else{
+            report!("* FAIL: Laser sampling errors are missing or have values outside {}", bounds);
+        }
+
+        if flags_ok {
+            if !clargs.failures_only{ report!("* PASS: Laser sampling errors are consistent with the laser sampling flags") };
+        }else{
+            report!("* FAIL: Laser sampling flags are missing, or lse is not consistent with them");
+        }
+    }
+
+    Ok(all_ok && flags_ok)
+}
+
+// lse values smaller than this are taken to mean no correction was applied
+const LSE_ZERO_TOLERANCE: f32 = 1e-6;
+
+fn check_laser_sampling_flags(nch: &netcdf::File, clargs: &CmdLineArgs) -> Result<bool, CheckError> {
+    // A set lsf means a laser sampling correction was applied, so lse should be nonzero;
+    // a clear one means it wasn't, so lse should be zero
+    let (lsf_var, lse_var) = match (nch.variable("lsf"), nch.variable("lse")) {
+        (Some(f), Some(e)) => (f, e),
+        _ => {
+            if clargs.verbosity >= 2 {
+                report!("  - FAIL: lsf and/or lse are not present, so the laser sampling flags cannot be checked");
+            }
+            return Ok(false);
+        }
+    };
+
+    let lsf_fill = _get_fill_value(&lsf_var);
+    let lse_fill = _get_fill_value(&lse_var);
+    let lsf_data = _get_record_data(&lsf_var, clargs)?;
+    let lse_data = _get_record_data(&lse_var, clargs)?;
+    _check_same_length("lsf", lsf_data.len(), "lse", lse_data.len())?;
+
+    let mut n_valid: usize = 0;
+    let mut n_set_zero: usize = 0;
+    let mut n_clear_nonzero: usize = 0;
+    for (&lsf, &lse) in lsf_data.iter().zip(lse_data.iter()) {
+        if _is_fill_or_nan(lsf, lsf_fill) || _is_fill_or_nan(lse, lse_fill) {
+            continue;
+        }
+        n_valid += 1;
+        let lse_is_zero = lse.abs() < LSE_ZERO_TOLERANCE;
+        if lsf != 0.0 && lse_is_zero {
+            n_set_zero += 1;
+        }else if lsf == 0.0 && !lse_is_zero {
+            n_clear_nonzero += 1;
+        }
+    }
+
+    let all_ok = n_set_zero == 0 && n_clear_nonzero == 0;
+    if clargs.verbosity >= 2 {
+        if all_ok {
+            if !clargs.failures_only{ report!("  - PASS: lse is nonzero exactly where lsf is set in all {} valid records", n_valid) };
+        }else{
+            if n_set_zero > 0 {
+                report!("  - FAIL: {}/{} records have lsf set but lse is zero", n_set_zero, n_valid);
+            }
+            if n_clear_nonzero > 0 {
+                report!("  - FAIL: {}/{} records have lsf clear but lse is nonzero", n_clear_nonzero, n_valid);
+            }
+        }
+    }
+
+    return Ok(all_ok);
+}
+
+
+fn check_averaging_kernels(nch: &netcdf::File, clargs: &CmdLineArgs) -> Result<bool, CheckError> {
+    if clargs.verbosity > 1 {
+        _print_header("\n=== Checking averaging kernels ===", clargs);
+    }
+
+    let grids_ok = check_altitude_grids(nch, clargs)?;
+    let bins_ok = check_ak_slant_bins(nch, clargs)?;
+    check_ak_slant_coverage(nch, clargs)?;
+
+    Ok(grids_ok && bins_ok)
+}
+
+fn check_ak_slant_coverage(nch: &netcdf::File, clargs: &CmdLineArgs) -> Result<(), CheckError> {
+    // The AKs are tabulated against slant column, so observed slant columns (ovc times airmass)
+    // outside the bins mean the AK had to be extrapolated. That is worth knowing but does not make
+    // the file wrong, so this only prints notes. Only windows whose main gas is the AK gas are used.
+    lazy_static! {
+        static ref RE: Regex = Regex::new(r"^ak_slant_x([a-z0-9]+)_bin$").unwrap();
+    }
+
+    let mut bin_vars: Vec<(String, String)> = nch.variables()
+        .filter_map(|var| {
+            let name = var.name();
+            RE.captures(&name).map(|caps| (caps[1].to_owned(), name.clone()))
+        })
+        .collect();
+    bin_vars.sort_unstable();
+    let ovc_family = _window_gas_family_vars(nch, "ovc");
+
+    for (gas, bin_varname) in bin_vars.iter() {
+        let bins = _get_float_data(&_get_var(nch, bin_varname)?, clargs)?;
+        let bin_min = bins.iter().copied().fold(f32::INFINITY, f32::min);
+        let bin_max = bins.iter().copied().fold(f32::NEG_INFINITY, f32::max);
+
+        let mut obs_min = f32::INFINITY;
+        let mut obs_max = f32::NEG_INFINITY;
+        let mut n_obs: usize = 0;
+        let mut n_outside: usize = 0;
+        for (label, ovc_varname) in ovc_family.iter() {
+            let window = match label.rsplit_once('/') {
+                Some((w, g)) if g == gas && w.starts_with(&format!("{}_", gas)) => w,
+                _ => continue
+            };
+            let am_var = match nch.variable(&format!("{}_am_{}", window, gas)) {
+                Some(v) => v,
+                None => continue
+            };
+
+            let ovc_data = _get_record_data(&_get_var(nch, ovc_varname)?, clargs)?;
+            let am_data = _get_record_data(&am_var, clargs)?;
+            for (&ovc, &am) in ovc_data.iter().zip(am_data.iter()) {
+                let slant = ovc * am;
+                // Non-positive columns and airmasses are failures for --check-ovc and --check-airmass
+                if !(slant.is_finite() && slant > 0.0) {
+                    continue;
+                }
+                n_obs += 1;
+                obs_min = obs_min.min(slant);
+                obs_max = obs_max.max(slant);
+                if slant < bin_min || slant > bin_max {
+                    n_outside += 1;
+                }
+            }
+        }
+
+        if n_outside > 0 {
+            if clargs.verbosity >= 1 {
+                report!("  NOTE: {}/{} x{} slant columns ({:e} to {:e}) are outside the AK slant bins ({:e} to {:e}), so their AKs are extrapolated",
+                         n_outside, n_obs, gas, obs_min, obs_max, bin_min, bin_max);
+            }
+        }else if n_obs > 0 && clargs.verbosity >= 2 && !clargs.failures_only {
+            report!("  - PASS: all {} x{} slant columns are within the AK slant bins", n_obs, gas);
+        }
+    }
+
+    return Ok(());
+}
+
+fn check_ak_slant_bins(nch: &netcdf::File, clargs: &CmdLineArgs) -> Result<bool, CheckError> {
+    lazy_static! {
+        static ref RE: Regex = Regex::new(r"^ak_slant_(x[a-z0-9]+)_bin$").unwrap();
+    }
+
+    let mut bin_vars: Vec<(String, String)> = nch.variables()
+        .filter_map(|var| {
+            let name = var.name();
+            RE.captures(&name).map(|caps| (caps[1].to_owned(), name.clone()))
+        })
+        .collect();
+    bin_vars.sort_unstable();
+
+    if bin_vars.is_empty() {
+        if clargs.verbosity >= 1 {
+            report!("* FAIL: no ak_slant_x<gas>_bin variables are present");
+        }
+        return Ok(false);
+    }
+
+    let mut all_ok = true;
+    for (gas, varname) in bin_vars.iter() {
+        let values = _get_float_data(&_get_var(nch, varname)?, clargs)?;
+        if let Some(i) = _first_non_increasing(&values) {
+            all_ok = false;
+            if clargs.verbosity >= 2 {
+                report!("  - FAIL: {} slant bins are not strictly increasing (first out of order at index {})", gas, i);
+            }
+        }else if clargs.verbosity >= 2 && !clargs.failures_only {
+            report!("  - PASS: {} slant bins are strictly increasing", gas);
+        }
+    }
+
+    if clargs.verbosity == 1 {
+        if all_ok {
+            if !clargs.failures_only{ report!("* PASS: AK slant column bins are increasing for all {} gases", bin_vars.len()) };
+        }else{
+            report!("* FAIL: AK slant column bins are not increasing for at least one gas");
+        }
+    }
+
+    Ok(all_ok)
+}
+
+fn check_altitude_grids(nch: &netcdf::File, clargs: &CmdLineArgs) -> Result<bool, CheckError> {
+    let mut all_ok = true;
+    let mut ranges: HashMap<&str, (f32, f32)> = HashMap::new();
+
+    for varname in ["ak_altitude", "prior_altitude"].iter() {
+        let var = match nch.variable(varname) {
+            Some(v) => v,
+            None => {
+                all_ok = false;
+                if clargs.verbosity >= 2 {
+                    report!("  - FAIL: variable '{}' is not present but should be", varname);
+                }
+                continue;
+            }
+        };
+
+        let values = _get_float_data(&var, clargs)?;
+        let min = values.iter().copied().fold(f32::INFINITY, f32::min);
+        let max = values.iter().copied().fold(f32::NEG_INFINITY, f32::max);
+        ranges.insert(varname, (min, max));
+
+        if let Some(i) = _first_non_increasing(&values) {
+            all_ok = false;
+            if clargs.verbosity >= 2 {
+                report!("  - FAIL: {} is not strictly increasing (first out of order at index {}; range {} to {})", varname, i, min, max);
+            }
+        }else if clargs.verbosity >= 2 && !clargs.failures_only {
+            report!("  - PASS: {} is strictly increasing (range {} to {})", varname, min, max);
+        }
+    }
+
+    // The AKs are interpolated to the prior levels, so must not need values outside them
+    if let (Some(ak), Some(prior)) = (ranges.get("ak_altitude"), ranges.get("prior_altitude")) {
+        if ak.0 < prior.0 || ak.1 > prior.1 {
+            all_ok = false;
+            if clargs.verbosity >= 2 {
+                report!("  - FAIL: ak_altitude ({} to {}) extends beyond prior_altitude ({} to {})", ak.0, ak.1, prior.0, prior.1);
+            }
+        }else if clargs.verbosity >= 2 && !clargs.failures_only {
+            report!("  - PASS: ak_altitude is within the range of prior_altitude");
+        }
+    }
+
+    if clargs.verbosity == 1 {
+        if all_ok {
+            if !clargs.failures_only{ report!("* PASS: AK and prior altitude grids are increasing and consistent") };
+        }else{
+            report!("* FAIL: AK and prior altitude grids are missing, not increasing, or inconsistent");
+        }
+    }
+
+    Ok(all_ok)
+}
+
+
+fn _ggg_gravity(geodetic_lat: f64, altitude_km: f64) -> f64 {
+    // The gravity GGG uses for its priors (gravity.f), including the centrifugal term
+    let gm: f64 = 3.9862216e14;     // Earth's gravitational constant times its mass (m3/s2)
+    let omega: f64 = 7.292116e-05;  // Earth's rotation rate (rad/s)
+    let con: f64 = 0.006738;        // (a/b)**2 - 1, where a and b are the equatorial and polar radii
+    let shc: f64 = 1.6235e-03;      // 2nd harmonic coefficient of Earth's gravity field
+    let eqrad: f64 = 6378178.0;     // Equatorial radius (m)
+
+    let gclat = (geodetic_lat.to_radians().tan() / (1.0 + con)).atan();
+    let radius = 1000.0 * altitude_km + eqrad / (1.0 + con * gclat.sin().powi(2)).sqrt();
+    let ff = (radius / eqrad).powi(2);
+    let hh = radius * omega.powi(2);
+    let ge = gm / eqrad.powi(2);
+    return (ge * (1.0 - shc * (3.0 * gclat.sin().powi(2) - 1.0) / ff) / ff - hh * gclat.cos().powi(2))
+        * (1.0 + 0.5 * (gclat.sin() * gclat.cos() * (hh / ge + 2.0 * shc / ff.powi(2))).powi(2));
+}
+
+fn check_gravity(nch: &netcdf::File, clargs: &CmdLineArgs) -> Result<bool, CheckError> {
+    if clargs.verbosity > 1 {
+        _print_header("\n=== Checking prior gravity ===", clargs);
+    }
+
+    let mut vars = Vec::new();
+    for varname in ["prior_gravity", "prior_effective_latitude", "prior_altitude"].iter() {
+        match nch.variable(varname) {
+            Some(v) => vars.push(v),
+            None => {
+                if clargs.verbosity >= 2 {
+                    report!("  - FAIL: variable '{}' is not present but should be", varname);
+                }
+            }
+        }
+    }
+    if vars.len() < 3 {
+        if clargs.verbosity == 1 {
+            report!("* FAIL: prior_gravity, prior_effective_latitude, and/or prior_altitude are missing");
+        }
+        return Ok(false);
+    }
+
+    let gravity = _get_record_data(&vars[0], clargs)?;
+    let lats = _get_record_data(&vars[1], clargs)?;
+    let alts = _get_record_data(&vars[2], clargs)?;
+    let grav_fill = _get_fill_value(&vars[0]);
+    // GGG writes the prior altitudes in km
+    let alt_scale = if matches!(_get_units(&vars[2]).as_deref(), Some("m") | Some("meters")) {0.001} else {1.0};
+
+    // prior_gravity is (prior_time, prior_altitude), while prior_altitude may be just the levels
+    // or the same shape as prior_gravity
+    if lats.is_empty() || gravity.len() % lats.len() != 0 || !(alts.len() == gravity.len() || alts.len() == gravity.len() / lats.len()) {
+        return Err(CheckError::Other(format!("prior_gravity ({} values) does not match prior_effective_latitude ({}) and prior_altitude ({})", gravity.len(), lats.len(), alts.len())));
+    }
+    let n_levels = gravity.len() / lats.len();
+
+    let mut n_valid: usize = 0;
+    let mut n_wrong: usize = 0;
+    let mut max_diff: f64 = 0.0;
+    for (i, &g) in gravity.iter().enumerate() {
+        let lat = lats[i / n_levels];
+        let alt = if alts.len() == gravity.len() {alts[i]} else {alts[i % n_levels]};
+        if _is_fill_or_nan(g, grav_fill) || lat.is_nan() || alt.is_nan() {
+            continue;
+        }
+
+        n_valid += 1;
+        let diff = (g as f64 - _ggg_gravity(lat as f64, alt as f64 * alt_scale)).abs();
+        max_diff = max_diff.max(diff);
+        if diff > clargs.gravity_tolerance as f64 {
+            n_wrong += 1;
+        }
+    }
+
+    let bounds = format!("{} m/s2 of the gravity computed from prior_effective_latitude and prior_altitude", clargs.gravity_tolerance);
+    let all_ok = _print_bounded_results("prior_gravity", n_valid, n_wrong, &bounds, clargs);
+    if clargs.verbosity >= 3 {
+        report!("    - largest difference is {:.6} m/s2", max_diff);
+    }
+
+    if clargs.verbosity == 1 {
+        if all_ok {
+            if !clargs.failures_only{ report!("* PASS: prior_gravity matches the gravity computed at the prior latitude and altitudes") };
+        }else{
+            report!("* FAIL: prior_gravity differs from the gravity computed at the prior latitude and altitudes by more than {} m/s2 (at most {:.4})", clargs.gravity_tolerance, max_diff);
+        }
+    }
+
+    Ok(all_ok)
+}
+
+fn check_h2o_dmf(nch: &netcdf::File, clargs: &CmdLineArgs) -> Result<bool, CheckError> {
+    if clargs.verbosity > 1 {
+        _print_header("\n=== Checking measured vs. model H2O ===", clargs);
+    }
+
+    let out_var = _get_var(nch, "h2o_dmf_out");
+    let mod_var = _get_var(nch, "h2o_dmf_mod");
+    let (out_var, mod_var) = match (out_var, mod_var) {
+        (Ok(o), Ok(m)) => (o, m),
+        _ => {
+            if clargs.verbosity >= 1 {
+                report!("* FAIL: h2o_dmf_out and/or h2o_dmf_mod are missing");
+            }
+            return Ok(false);
+        }
+    };
+
+    let out_fill = _get_fill_value(&out_var);
+    let mod_fill = _get_fill_value(&mod_var);
+    let out_data = _get_record_data(&out_var, clargs)?;
+    let mod_data = _get_record_data(&mod_var, clargs)?;
+    _check_same_length("h2o_dmf_out", out_data.len(), "h2o_dmf_mod", mod_data.len())?;
+
+    let threshold = clargs.h2o_dmf_threshold;
+    let mut n_valid: usize = 0;
+    let mut n_wrong: usize = 0;
+    for (&meas, &model) in out_data.iter().zip(mod_data.iter()) {
+        // A zero model value has no meaningful relative difference, so skip it like fills
+        if _is_fill_or_nan(meas, out_fill) || _is_fill_or_nan(model, mod_fill) || model == 0.0 {
+            continue;
+        }
+
+        n_valid += 1;
+        if ((meas - model) / model).abs() > threshold {
+            n_wrong += 1;
+        }
+    }
+
+    let all_ok = n_wrong == 0;
+    if clargs.verbosity >= 2 {
+        if all_ok {
+            if !clargs.failures_only{ report!("  - PASS: h2o_dmf_out and h2o_dmf_mod agree within {}% in all {} valid records", threshold * 100.0, n_valid) };
+        }else{
+            report!("  - FAIL: {}/{} ({}) valid records have h2o_dmf_out and h2o_dmf_mod differing by more than {}%", n_wrong, n_valid, _format_percent(n_wrong, n_valid), threshold * 100.0);
+        }
+    }else if clargs.verbosity == 1 {
+        if all_ok {
+            if !clargs.failures_only{ report!("* PASS: Measured and model H2O agree within tolerance") };
+        }else{
+            report!("* FAIL: Measured and model H2O disagree by more than {}% in {} records", threshold * 100.0, n_wrong);
+        }
+    }
+
+    Ok(all_ok)
+}
+
+
+fn check_met(nch: &netcdf::File, clargs: &CmdLineArgs) -> Result<bool, CheckError> {
+    if clargs.verbosity > 1 {
+        _print_header("\n=== Checking meteorology ===", clargs);
+    }
+
+    let model_ok = check_model_met(nch, clargs)?;
+    let wind_ok = check_wind_pairing(nch, clargs)?;
+
+    Ok(model_ok && wind_ok)
+}
+
+fn _temperature_bounds(units: Option<&str>) -> (f32, f32) {
+    // About the coldest and hottest surface temperatures on record, with some margin.
+    // GGG writes temperatures in degrees C, so that is assumed if there are no units.
+    match units {
+        Some("K") | Some("kelvin") => (183.0, 333.0),
+        _ => (-90.0, 60.0)
+    }
+}
+
+fn _pressure_bounds(units: Option<&str>) -> (f32, f32) {
+    // From a high mountain site to above any sea-level pressure; GGG uses hPa
+    match units {
+        Some("Pa") => (30000.0, 110000.0),
+        Some("atm") => (0.3, 1.1),
+        _ => (300.0, 1100.0)
+    }
+}
+
+fn check_model_met(nch: &netcdf::File, clargs: &CmdLineArgs) -> Result<bool, CheckError> {
+    let mut all_ok = true;
+    for varname in ["tmod", "pmod"].iter() {
+        let var_ok = match nch.variable(varname) {
+            Some(var) => {
+                let units = _get_units(&var);
+                let (min, max) = if *varname == "tmod" {
+                    _temperature_bounds(units.as_deref())
+                }else{
+                    _pressure_bounds(units.as_deref())
+                };
+                let (n_total, n_wrong) = _count_out_of_range(&var, Some(min), Some(max), clargs)?;
+                let bounds = format!("[{}, {}] {}", min, max, units.as_deref().unwrap_or("(no units)"));
+                _print_bounded_results(varname, n_total, n_wrong, &bounds, clargs)
+            },
+            None => {
+                if clargs.verbosity >= 2 {
+                    report!("  - FAIL: variable '{}' is not present but should be", varname);
+                }
+                false
+            }
+        };
+        all_ok = all_ok && var_ok;
+    }
+
+    if clargs.verbosity == 1 {
+        if all_ok {
+            if !clargs.failures_only{ report!("* PASS: Model temperature and pressure are present and plausible") };
+        }else{
+            report!("* FAIL: Model temperature and/or pressure are missing or have implausible values");
+        }
+    }
+
+    Ok(all_ok)
+}
+
+fn check_wind_pairing(nch: &netcdf::File, clargs: &CmdLineArgs) -> Result<bool, CheckError> {
+    // Any real wind has a direction, while a calm wind has none, so sites conventionally
+    // write 0 or a fill value for wdir when wspd is zero. Records with a fill wspd are skipped.
+    let (spd_var, dir_var) = match (nch.variable("wspd"), nch.variable("wdir")) {
+        (Some(s), Some(d)) => (s, d),
+        _ => {
+            if clargs.verbosity >= 2 {
+                report!("  - FAIL: wspd and/or wdir are not present but should be");
+            }
+            if clargs.verbosity == 1 {
+                report!("* FAIL: Wind speed and/or direction are missing");
+            }
+            return Ok(false);
+        }
+    };
+
+    let spd_fill = _get_fill_value(&spd_var);
+    let dir_fill = _get_fill_value(&dir_var);
+    let spd_data = _get_record_data(&spd_var, clargs)?;
+    let dir_data = _get_record_data(&dir_var, clargs)?;
+    _check_same_length("wspd", spd_data.len(), "wdir", dir_data.len())?;
+
+    let mut n_windy: usize = 0;
+    let mut n_bad_dir: usize = 0;
+    let mut n_calm: usize = 0;
+    let mut n_bad_calm: usize = 0;
+    for (&spd, &dir) in spd_data.iter().zip(dir_data.iter()) {
+        if _is_fill_or_nan(spd, spd_fill) {
+            continue;
+        }
+        if spd > 0.0 {
+            n_windy += 1;
+            if _is_fill_or_nan(dir, dir_fill) || !(0.0..=360.0).contains(&dir) {
+                n_bad_dir += 1;
+            }
+        }else{
+            n_calm += 1;
+            if clargs.calm_wdir == "zero" && !_is_fill_or_nan(dir, dir_fill) && dir != 0.0 {
+                n_bad_calm += 1;
+            }
+        }
+    }
+
+    let windy_ok = _print_bounded_results("wdir where wspd > 0", n_windy, n_bad_dir, "[0, 360]", clargs);
+    let calm_ok = if clargs.calm_wdir == "zero" {
+        _print_bounded_results("wdir where wspd = 0", n_calm, n_bad_calm, "0 or fill", clargs)
+    }else{
+        true
+    };
+
+    let all_ok = windy_ok && calm_ok;
+    if clargs.verbosity == 1 {
+        if all_ok {
+            if !clargs.failures_only{ report!("* PASS: Wind direction is consistent with wind speed") };
+        }else{
+            report!("* FAIL: Wind direction is inconsistent with wind speed in at least one record");
+        }
+    }
+
+    Ok(all_ok)
+}
+
+fn check_pointing(nch: &netcdf::File, clargs: &CmdLineArgs) -> Result<bool, CheckError> {
+    if clargs.verbosity > 1 {
+        _print_header("\n=== Checking pointing diagnostics ===", clargs);
+    }
+
+    // dip is a fractional intensity dip so can be either sign; mvd is a maximum velocity
+    // displacement, so should never be negative
+    let limits = [("dip", -clargs.dip_max, clargs.dip_max), ("mvd", 0.0, clargs.mvd_max)];
+    let mut all_ok = true;
+    for (varname, min, max) in limits.iter() {
+        let var_ok = match nch.variable(varname) {
+            Some(var) => {
+                let (n_total, n_wrong) = _count_out_of_range(&var, Some(*min), Some(*max), clargs)?;
+                let bounds = format!("[{}, {}]", min, max);
+                _print_bounded_results(varname, n_total, n_wrong, &bounds, clargs)
+            },
+            None => {
+                if clargs.verbosity >= 2 {
+                    report!("  - FAIL: variable '{}' is not present but should be", varname);
+                }
+                false
+            }
+        };
+        all_ok = all_ok && var_ok;
+    }
+
+    if clargs.verbosity == 1 {
+        if all_ok {
+            if !clargs.failures_only{ report!("* PASS: Pointing diagnostics dip and mvd are present and within bounds") };
+        }else{
+            report!("* FAIL: Pointing diagnostics dip and/or mvd are missing or out of bounds");
+        }
+    }
+
+    Ok(all_ok)
+}
+
+
+fn check_graw_opd(nch: &netcdf::File, clargs: &CmdLineArgs) -> Result<bool, CheckError> {
+    if clargs.verbosity > 1 {
+        _print_header("\n=== Checking instrument parameters ===", clargs);
+    }
+
+    // The raw spectral point spacing is inversely proportional to the maximum OPD, but the constant
+    // depends on the instrument's sampling (nominally 1/2), so without --graw-opd-product the file's
+    // median is taken as the expected value, which catches individual inconsistent records
+    let (graw_var, opd_var) = match (nch.variable("graw"), nch.variable("opd")) {
+        (Some(g), Some(o)) => (g, o),
+        _ => {
+            if clargs.verbosity >= 2 {
+                report!("  - FAIL: graw and/or opd are not present but should be");
+            }
+            if clargs.verbosity == 1 {
+                report!("* FAIL: graw and/or opd are missing");
+            }
+            return Ok(false);
+        }
+    };
+
+    let graw_fill = _get_fill_value(&graw_var);
+    let opd_fill = _get_fill_value(&opd_var);
+    let graw_data = _get_record_data(&graw_var, clargs)?;
+    let opd_data = _get_record_data(&opd_var, clargs)?;
+    _check_same_length("graw", graw_data.len(), "opd", opd_data.len())?;
+
+    // Keep the record index so that the first offender can be reported
+    let products: Vec<(usize, f32)> = graw_data.iter().zip(opd_data.iter()).enumerate()
+        .filter(|(_, (&g, &o))| !_is_fill_or_nan(g, graw_fill) && !_is_fill_or_nan(o, opd_fill))
+        .map(|(i, (&g, &o))| (i, g * o))
+        .collect();
+
+    let expected = match clargs.graw_opd_product {
+        Some(p) => p,
+        None => {
+            let mut sorted: Vec<f32> = products.iter().map(|&(_, p)| p).collect();
+            sorted.sort_unstable_by(|a, b| a.total_cmp(b));
+            if sorted.is_empty() {0.0} else {sorted[sorted.len() / 2]}
+        }
+    };
+
+    let tol = clargs.graw_opd_tolerance;
+    let wrong: Vec<&(usize, f32)> = products.iter().filter(|(_, p)| (p - expected).abs() > tol * expected.abs()).collect();
+    let bounds = format!("{}% of {}", tol * 100.0, expected);
+    let all_ok = _print_bounded_results("graw * opd", products.len(), wrong.len(), &bounds, clargs) && !products.is_empty();
+    if let Some((i, p)) = wrong.first() {
+        if clargs.verbosity >= 2 {
+            report!("  - first inconsistent record is {} (graw = {}, opd = {}, graw * opd = {})", _file_record_index(&graw_var, *i, clargs), graw_data[*i], opd_data[*i], p);
+        }
+    }
+
+    if clargs.verbosity == 1 {
+        if all_ok {
+            if !clargs.failures_only{ report!("* PASS: graw and opd are consistent in all {} valid records", products.len()) };
+        }else{
+            report!("* FAIL: graw * opd differs from {} by more than {}% in {}/{} records", expected, tol * 100.0, wrong.len(), products.len());
+        }
+    }
+
+    Ok(all_ok)
+}
+
+fn check_solar_intensity(nch: &netcdf::File, clargs: &CmdLineArgs) -> Result<bool, CheckError> {
+    if clargs.verbosity > 1 {
+        _print_header("\n=== Checking solar intensity diagnostics ===", clargs);
+    }
+
+    // sia must be strictly positive; the smallest positive float as the minimum makes 0 fail
+    let limits = [
+        ("sia", Some(f32::MIN_POSITIVE), None, String::from("(0, inf)")),
+        ("fvsi", Some(0.0), Some(clargs.fvsi_max), format!("[0, {}]", clargs.fvsi_max))
+    ];
+    let mut all_ok = true;
+    for (varname, min, max, bounds) in limits.iter() {
+        let var_ok = match nch.variable(varname) {
+            Some(var) => {
+                let (n_total, n_wrong) = _count_out_of_range(&var, *min, *max, clargs)?;
+                _print_bounded_results(varname, n_total, n_wrong, bounds, clargs)
+            },
+            None => {
+                if clargs.verbosity >= 2 {
+                    report!("  - FAIL: variable '{}' is not present but should be", varname);
+                }
+                false
+            }
+        };
+        all_ok = all_ok && var_ok;
+    }
+
+    let filter_ok = match (clargs.expected_fvsi_max, nch.variable("fvsi")) {
+        (Some(threshold), Some(var)) => check_fvsi_filter(&var, threshold, clargs)?,
+        _ => true
+    };
+
+    if clargs.verbosity == 1 {
+        if !filter_ok {
+            report!("* FAIL: fvsi exceeds the expected QA filter threshold of {}, so the filter does not appear to have been applied", clargs.expected_fvsi_max.unwrap());
+        }
+        if all_ok {
+            if !clargs.failures_only{ report!("* PASS: Solar intensity diagnostics sia and fvsi are present and within bounds") };
+        }else{
+            report!("* FAIL: Solar intensity diagnostics sia and/or fvsi are missing or out of bounds");
+        }
+    }
+
+    Ok(all_ok && filter_ok)
+}
+
+fn check_fvsi_filter(var: &netcdf::Variable, threshold: f32, clargs: &CmdLineArgs) -> Result<bool, CheckError> {
+    // Unlike --fvsi-max, this is what the archive claims was already filtered out, so one record over it is evidence
+    let values = _get_record_data(var, clargs)?;
+    let fill = _get_fill_value(var);
+    let mut n_valid = 0;
+    let mut n_over = 0;
+    let mut max_fvsi = f32::NEG_INFINITY;
+    for (i, &v) in values.iter().enumerate() {
+        if _is_fill_or_nan(v, fill) {
+            continue;
+        }
+        n_valid += 1;
+        max_fvsi = max_fvsi.max(v);
+        if v > threshold {
+            n_over += 1;
+            if clargs.verbosity >= 3 && n_over <= 10 {
+                report!("    - record {}: fvsi = {}", _file_record_index(var, i, clargs), v);
+            }else if clargs.verbosity >= 3 && n_over == 11 {
+                report!("    (further records omitted)");
+            }
+        }
+    }
+
+    let all_ok = n_over == 0;
+    if clargs.verbosity >= 2 {
+        if all_ok {
+            if !clargs.failures_only{ report!("  - PASS: no fvsi is above the expected QA filter threshold of {}", threshold) };
+        }else{
+            report!("  - FAIL: {}/{} fvsi values are above the expected QA filter threshold of {} (max {})", n_over, n_valid, threshold, max_fvsi);
+        }
+    }
+
+    return Ok(all_ok);
+}
+
+fn _azim_bounds(convention: &str) -> (f32, f32) {
+    // Lower bound inclusive, upper exclusive; "either" allows a file using both conventions
+    match convention {
+        "signed" => (-180.0, 180.0),
+        "either" => (-180.0, 360.0),
+        _ => (0.0, 360.0)
+    }
+}
+
+fn check_geolocation(nch: &netcdf::File, clargs: &CmdLineArgs) -> Result<bool, CheckError> {
+    if clargs.verbosity > 1 {
+        _print_header("\n=== Checking geolocation ===", clargs);
+    }
+
+    let azim_ok = match nch.variable("azim") {
+        Some(var) => {
+            let (min, max) = _azim_bounds(&clargs.azim_convention);
+            let values = _get_record_data(&var, clargs)?;
+            let n_wrong = values.iter().filter(|&&v| v.is_nan() || v < min || v >= max).count();
+            _print_bounded_results("azim", values.len(), n_wrong, &format!("[{}, {})", min, max), clargs)
+        },
+        None => {
+            if clargs.verbosity >= 2 {
+                report!("  - FAIL: variable 'azim' is not present but should be");
+            }
+            false
+        }
+    };
+
+    if clargs.verbosity == 1 {
+        if azim_ok {
+            if !clargs.failures_only{ report!("* PASS: Solar azimuth is present and within range") };
+        }else{
+            report!("* FAIL: Solar azimuth is missing or out of range");
+        }
+    }
+
+    Ok(azim_ok)
+}
+
+fn _hf_limit(limits: &[(f32, f32)], lat: f32) -> f32 {
+    // Bands are sorted by their upper |latitude|; anything poleward of the last uses the last
+    let abs_lat = lat.abs();
+    limits.iter().find(|(band_lat, _)| abs_lat <= *band_lat).unwrap_or_else(|| limits.last().unwrap()).1
+}
+
+fn check_hf_sanity(nch: &netcdf::File, clargs: &CmdLineArgs) -> Result<bool, CheckError> {
+    if clargs.verbosity > 1 {
+        _print_header("\n=== Checking xhf against latitude ===", clargs);
+    }
+
+    // HF is a stratospheric tracer, so its column increases poleward. This is only a coarse
+    // screen for archive QA, so unless --hf-fail is given, high values are notes, not failures.
+    let (xhf_var, lat_var) = match (nch.variable("xhf"), nch.variable("lat")) {
+        (Some(x), Some(l)) => (x, l),
+        _ => {
+            if clargs.verbosity >= 2 {
+                report!("  - FAIL: xhf and/or lat are not present but should be");
+            }else if clargs.verbosity == 1 {
+                report!("* FAIL: xhf and/or lat are missing, so xhf cannot be checked against latitude");
+            }
+            return Ok(false);
+        }
+    };
+    let xhf = _get_record_data(&xhf_var, clargs)?;
+    let xhf_fill = _get_fill_value(&xhf_var);
+    let lat = _get_record_data(&lat_var, clargs)?;
+    _check_same_length("xhf", xhf.len(), "lat", lat.len())?;
+
+    let mut n_valid: usize = 0;
+    let mut n_high: usize = 0;
+    let mut worst: Option<(f32, f32, f32)> = None;
+    for (&x, &y) in xhf.iter().zip(lat.iter()) {
+        if _is_fill_or_nan(x, xhf_fill) || y.is_nan() {
+            continue;
+        }
+        n_valid += 1;
+        let limit = _hf_limit(&clargs.hf_limits, y);
+        if x > limit {
+            n_high += 1;
+            if worst.is_none_or(|(wx, _, wl)| x - limit > wx - wl) {
+                worst = Some((x, y, limit));
+            }
+        }
+    }
+
+    let all_ok = n_high == 0 || !clargs.hf_fail;
+    if n_high == 0 {
+        if clargs.verbosity >= 1 && !clargs.failures_only {
+            let prefix = if clargs.verbosity == 1 {"*"} else {"  -"};
+            report!("{} PASS: all {} xhf values are plausible for their latitude", prefix, n_valid);
+        }
+    }else{
+        let percent = _format_percent(n_high, n_valid);
+        if clargs.verbosity >= 1 {
+            if clargs.hf_fail {
+                let prefix = if clargs.verbosity == 1 {"*"} else {"  -"};
+                report!("{} FAIL: {}/{} ({}) xhf values are implausibly high for their latitude", prefix, n_high, n_valid, percent);
+            }else{
+                report!("  NOTE: {}/{} ({}) xhf values are implausibly high for their latitude", n_high, n_valid, percent);
+            }
+        }
+        if clargs.verbosity == 3 {
+            let (x, y, limit) = worst.unwrap();
+            report!("    - largest excess: xhf = {} at lat = {} (limit {})", x, y, limit);
+        }
+    }
+
+    Ok(all_ok)
+}
+
+// The O2 dry mole fraction GGG assumes when converting columns to Xgas
+const O2_DRY_MOLE_FRACTION: f32 = 0.2095;
+
+fn check_o2_consistency(nch: &netcdf::File, clargs: &CmdLineArgs) -> Result<bool, CheckError> {
+    if clargs.verbosity > 1 {
+        _print_header("\n=== Checking O2 consistency ===", clargs);
+    }
+
+    let tol = clargs.o2_tolerance;
+    let (min, max) = (O2_DRY_MOLE_FRACTION * (1.0 - tol), O2_DRY_MOLE_FRACTION * (1.0 + tol));
+    let bounds = format!("[{}, {}]", min, max);
+
+    let xo2_ok = match nch.variable("xo2") {
+        Some(var) => {
+            let (n_total, n_wrong) = _count_out_of_range(&var, Some(min), Some(max), clargs)?;
+            _print_bounded_results("xo2", n_total, n_wrong, &bounds, clargs)
+        },
+        None => {
+            if clargs.verbosity >= 2 {
+                report!("  - FAIL: variable 'xo2' is not present but should be");
+            }
+            false
+        }
+    };
+
+    // column_luft is the dry air column, so the ratio is the retrieved O2 mole fraction
+    let ratio_ok = match (nch.variable("column_o2"), nch.variable("column_luft")) {
+        (Some(o2_var), Some(luft_var)) => {
+            let o2_fill = _get_fill_value(&o2_var);
+            let luft_fill = _get_fill_value(&luft_var);
+            let o2_data = _get_record_data(&o2_var, clargs)?;
+            let luft_data = _get_record_data(&luft_var, clargs)?;
+            _check_same_length("column_o2", o2_data.len(), "column_luft", luft_data.len())?;
+
+            let mut n_valid: usize = 0;
+            let mut n_wrong: usize = 0;
+            for (&o2, &luft) in o2_data.iter().zip(luft_data.iter()) {
+                if _is_fill_or_nan(o2, o2_fill) || _is_fill_or_nan(luft, luft_fill) || luft == 0.0 {
+                    continue;
+                }
+                n_valid += 1;
+                let ratio = o2 / luft;
+                if ratio < min || ratio > max {
+                    n_wrong += 1;
+                }
+            }
+
+            _print_bounded_results("column_o2/column_luft", n_valid, n_wrong, &bounds, clargs)
+        },
+        _ => {
+            if clargs.verbosity >= 2 {
+                report!("  - FAIL: column_o2 and/or column_luft are not present but should be");
+            }
+            false
+        }
+    };
+
+    let all_ok = xo2_ok && ratio_ok;
+    if clargs.verbosity == 1 {
+        if all_ok {
+            if !clargs.failures_only{ report!("* PASS: xo2 and column_o2/column_luft are consistent with an O2 mole fraction of {}", O2_DRY_MOLE_FRACTION) };
+        }else{
+            report!("* FAIL: xo2 and/or column_o2/column_luft are missing or differ from {} by more than {}%", O2_DRY_MOLE_FRACTION, tol * 100.0);
+        }
+    }
+
+    Ok(all_ok)
+}
+
+fn check_cell_block(nch: &netcdf::File, clargs: &CmdLineArgs) -> Result<bool, CheckError> {
+    if clargs.verbosity > 1 {
+        _print_header("\n=== Checking gas cell parameters ===", clargs);
+    }
+
+    let cell_var = match nch.variable("cell_index") {
+        Some(v) => v,
+        None => {
+            if clargs.verbosity >= 1 {
+                report!("* FAIL: cell_index is missing");
+            }
+            return Ok(false);
+        }
+    };
+    let cell_index = _get_float_data(&cell_var, clargs)?;
+    let n_cells = cell_index.len();
+
+    let mut all_ok = true;
+    for varname in ["cell_temperature", "cell_pressure", "cell_density"].iter() {
+        let var = match nch.variable(varname) {
+            Some(v) => v,
+            None => {
+                all_ok = false;
+                if clargs.verbosity >= 2 {
+                    report!("  - FAIL: variable '{}' is not present but should be", varname);
+                }
+                continue;
+            }
+        };
+
+        // Find which element of cell_index each value belongs to, allowing for the
+        // cell variables having other dimensions before or after the cell one
+        let dims = var.dimensions();
+        let cell_dim = match dims.iter().position(|d| d.len() == n_cells && d.name().starts_with("cell")) {
+            Some(i) => i,
+            None => {
+                all_ok = false;
+                if clargs.verbosity >= 2 {
+                    report!("  - FAIL: {} does not have a dimension matching cell_index", varname);
+                }
+                continue;
+            }
+        };
+        let stride: usize = dims[cell_dim+1..].iter().map(|d| d.len()).product();
+
+        let fill = _get_fill_value(&var);
+        let data = _get_float_data(&var, clargs)?;
+        let mut n_bad_used: usize = 0;
+        let mut n_bad_unused: usize = 0;
+        for (k, &value) in data.iter().enumerate() {
+            let in_use = cell_index[(k / stride.max(1)) % n_cells] != 0.0;
+            let is_empty = _is_fill_or_nan(value, fill) || value == 0.0;
+            if in_use && (is_empty || value < 0.0) {
+                n_bad_used += 1;
+            }else if !in_use && !is_empty {
+                n_bad_unused += 1;
+            }
+        }
+
+        let var_ok = n_bad_used == 0 && n_bad_unused == 0;
+        all_ok = all_ok && var_ok;
+        if clargs.verbosity >= 2 {
+            if n_bad_used > 0 {
+                report!("  - FAIL: {}/{} {} values are missing or not positive where cell_index is nonzero", n_bad_used, data.len(), varname);
+            }
+            if n_bad_unused > 0 {
+                report!("  - FAIL: {}/{} {} values are neither fill nor zero where cell_index is zero", n_bad_unused, data.len(), varname);
+            }
+            if var_ok && !clargs.failures_only {
+                report!("  - PASS: {} is consistent with cell_index", varname);
+            }
+        }
+    }
+
+    if clargs.verbosity == 1 {
+        if all_ok {
+            if !clargs.failures_only{ report!("* PASS: Gas cell temperature, pressure, and density are consistent with cell_index") };
+        }else{
+            report!("* FAIL: Gas cell temperature, pressure, and/or density are missing or inconsistent with cell_index");
+        }
+    }
+
+    Ok(all_ok)
+}
+
+fn _window_family_vars(nch: &netcdf::File, suffix: &str) -> Vec<(String, String)> {
+    // The per-window fit outputs are named <window>_<suffix>, e.g. co2_6220_fs
+    let mut family: Vec<(String, String)> = nch.variables()
+        .filter_map(|var| {
+            let varname = var.name();
+            let window = _window_block(&varname)?.to_owned();
+            if varname[window.len()+1..] == *suffix {
+                Some((window, varname))
+            }else{
+                None
+            }
+        })
+        .collect();
+    family.sort_unstable();
+    return family;
+}
+
+fn _window_gas_family_vars(nch: &netcdf::File, kind: &str) -> Vec<(String, String)> {
+    // Per window and gas outputs are named <window>_<kind>_<gas>, e.g. co2_6220_am_co2. The
+    // items are labeled <window>/<gas>. Anything after the gas (e.g. _error) is a different family.
+    let prefix = format!("{}_", kind);
+    let mut family: Vec<(String, String)> = nch.variables()
+        .filter_map(|var| {
+            let varname = var.name();
+            let window = _window_block(&varname)?;
+            let gas = varname[window.len()+1..].strip_prefix(&prefix)?;
+            if !gas.is_empty() && gas.chars().all(|c| c.is_ascii_alphanumeric()) {
+                Some((format!("{}/{}", window, gas), varname.clone()))
+            }else{
+                None
+            }
+        })
+        .collect();
+    family.sort_unstable();
+    return family;
+}
+
+struct WindowFamilyResults {
+    all_ok: bool,
+    n_windows: usize,
+    bounds: String,
+    // The window with the largest fraction of records out of bounds, with its total and out of bounds counts
+    worst: Option<(String, usize, usize)>
+}
+
+fn _check_window_family_bounded(nch: &netcdf::File, suffix: &str, min: Option<f32>, max: Option<f32>, clargs: &CmdLineArgs) -> Result<WindowFamilyResults, CheckError> {
+    let bounds = format!("[{}, {}]", min.map_or(String::from("-inf"), |v| v.to_string()), max.map_or(String::from("inf"), |v| v.to_string()));
+    let family = _window_family_vars(nch, suffix);
+    return _check_family_bounded(nch, &family, min, max, bounds, clargs);
+}
+
+fn _check_family_bounded(nch: &netcdf::File, family: &[(String, String)], min: Option<f32>, max: Option<f32>, bounds: String, clargs: &CmdLineArgs) -> Result<WindowFamilyResults, CheckError> {
+    let mut all_ok = true;
+    let mut worst: Option<(String, usize, usize)> = None;
+    for (window, varname) in family.iter() {
+        let var = _get_var(nch, varname)?;
+        let (n_total, n_wrong) = _count_out_of_range(&var, min, max, clargs)?;
+        all_ok = _print_bounded_results(varname, n_total, n_wrong, &bounds, clargs) && all_ok;
+        _update_worst_window(&mut worst, window, n_total, n_wrong);
+    }
+
+    return Ok(WindowFamilyResults{all_ok: all_ok && !family.is_empty(), n_windows: family.len(), bounds: bounds, worst: worst});
+}
+
+fn _check_positive_family(nch: &netcdf::File, family: &[(String, String)], clargs: &CmdLineArgs) -> Result<WindowFamilyResults, CheckError> {
+    // Strictly positive and finite: the smallest positive float as the minimum makes 0
+    // fail and the largest float as the maximum makes +inf fail
+    return _check_family_bounded(nch, family, Some(f32::MIN_POSITIVE), Some(f32::MAX), String::from("(0, inf)"), clargs);
+}
+
+fn _update_worst_window(worst: &mut Option<(String, usize, usize)>, window: &str, n_total: usize, n_wrong: usize) {
+    let frac = n_wrong as f32 / n_total.max(1) as f32;
+    let is_worse = match worst {
+        Some((_, w_total, w_wrong)) => frac > *w_wrong as f32 / (*w_total).max(1) as f32,
+        None => n_wrong > 0
+    };
+    if is_worse {
+        *worst = Some((window.to_owned(), n_total, n_wrong));
+    }
+}
+
+fn _print_window_family_summary(description: &str, suffix: &str, results: &WindowFamilyResults, clargs: &CmdLineArgs) {
+    let bounds = &results.bounds;
+    if results.n_windows == 0 {
+        if clargs.verbosity >= 1 {
+            report!("* FAIL: no <window>_{} variables are present", suffix);
+        }
+        return;
+    }
+
+    match &results.worst {
+        Some((window, n_total, n_wrong)) => {
+            if clargs.verbosity >= 2 {
+                report!("  - worst window is {} ({}/{} records outside {})", window, n_wrong, n_total, bounds);
+            }else if clargs.verbosity == 1 {
+                report!("* FAIL: {} are outside {} in at least one window (worst is {}, {}/{} records)", description, bounds, window, n_wrong, n_total);
+            }
+        },
+        None => {
+            if clargs.verbosity == 1 && !clargs.failures_only {
+                report!("* PASS: {} are within {} for all {} windows", description, bounds, results.n_windows);
+            }
+        }
+    }
+}
+
+fn check_freq_shifts(nch: &netcdf::File, clargs: &CmdLineArgs) -> Result<bool, CheckError> {
+    if clargs.verbosity > 1 {
+        _print_header("\n=== Checking frequency shifts ===", clargs);
+    }
+
+    let results = _check_window_family_bounded(nch, "fs", Some(-clargs.fs_threshold), Some(clargs.fs_threshold), clargs)?;
+    _print_window_family_summary("Frequency shifts", "fs", &results, clargs);
+
+    Ok(results.all_ok)
+}
+
+fn check_rmsocl(nch: &netcdf::File, clargs: &CmdLineArgs) -> Result<bool, CheckError> {
+    if clargs.verbosity > 1 {
+        _print_header("\n=== Checking fit residuals ===", clargs);
+    }
+
+    let results = _check_window_family_bounded(nch, "rmsocl", Some(0.0), Some(clargs.rms_threshold), clargs)?;
+
+    // Non-finite residuals are already counted as out of bounds, but mean the fit failed
+    // outright rather than was just poor, so are worth calling out on their own
+    let mut n_nonfinite_windows = 0;
+    for (_, varname) in _window_family_vars(nch, "rmsocl").iter() {
+        let values = _get_record_data(&_get_var(nch, varname)?, clargs)?;
+        let n_nonfinite = values.iter().filter(|v| !v.is_finite()).count();
+        if n_nonfinite > 0 {
+            n_nonfinite_windows += 1;
+            if clargs.verbosity >= 2 {
+                report!("  - FAIL: {} has {}/{} non-finite values", varname, n_nonfinite, values.len());
+            }
+        }
+    }
+
+    if n_nonfinite_windows > 0 && clargs.verbosity == 1 {
+        report!("* FAIL: {} windows have non-finite fit residuals", n_nonfinite_windows);
+    }
+    _print_window_family_summary("Fit residuals (rmsocl)", "rmsocl", &results, clargs);
+
+    Ok(results.all_ok)
+}
+
+fn check_iterations(nch: &netcdf::File, clargs: &CmdLineArgs) -> Result<bool, CheckError> {
+    if clargs.verbosity > 1 {
+        _print_header("\n=== Checking fit iterations ===", clargs);
+    }
+
+    // A fit that used the maximum number of iterations stopped without converging
+    let max_ok = clargs.nit_max.saturating_sub(1) as f32;
+    let results = _check_window_family_bounded(nch, "nit", Some(0.0), Some(max_ok), clargs)?;
+    _print_window_family_summary("Fit iteration counts (nit)", "nit", &results, clargs);
+
+    Ok(results.all_ok)
+}
+
+fn check_continuum(nch: &netcdf::File, windows: &HashMap<String, Window>, clargs: &CmdLineArgs) -> Result<bool, CheckError> {
+    if clargs.verbosity > 1 {
+        _print_header("\n=== Checking continuum fits ===", clargs);
+    }
+
+    let families = [
+        ("ct", "Continuum tilts (ct)", clargs.ct_max),
+        ("cc", "Continuum curvatures (cc)", clargs.cc_max)
+    ];
+    let mut all_ok = true;
+    for (suffix, description, max) in families.iter() {
+        let results = _check_window_family_bounded(nch, suffix, Some(-max), Some(*max), clargs)?;
+        _print_window_family_summary(description, suffix, &results, clargs);
+        all_ok = all_ok && results.all_ok;
+    }
+
+    let level_ok = check_continuum_level(nch, clargs)?;
+    let cbf_ok = check_continuum_basis_functions(nch, windows, clargs)?;
+
+    Ok(all_ok && level_ok && cbf_ok)
+}
+
+fn check_continuum_basis_functions(nch: &netcdf::File, windows: &HashMap<String, Window>, clargs: &CmdLineArgs) -> Result<bool, CheckError> {
+    // A window fits as many continuum basis functions as its ncbf=N in the windows table, so any
+    // <window>_cbf_NN beyond N must be absent or all zero
+    lazy_static! {
+        static ref RE: Regex = Regex::new(r"_cbf_(\d+)$").unwrap();
+    }
+    let mut file_cbfs: HashMap<&str, Vec<(usize, String)>> = HashMap::new();
+    for var in nch.variables() {
+        let varname = var.name();
+        if let (Some(window), Some(caps)) = (_window_block(&varname), RE.captures(&varname)) {
+            if let Some((win_name, _)) = windows.get_key_value(window) {
+                let n: usize = caps.get(1).unwrap().as_str().parse().unwrap();
+                file_cbfs.entry(win_name.as_str()).or_default().push((n, varname.clone()));
+            }
+        }
+    }
+
+    let mut win_names: Vec<&str> = windows.keys().map(|x| x.as_ref()).collect();
+    win_names.sort_unstable();
+
+    let mut all_ok = true;
+    for win in win_names {
+        let ncbf = match windows[win].params.iter().find_map(|p| p.strip_prefix("ncbf=")).and_then(|n| n.parse::<usize>().ok()) {
+            Some(n) => n,
+            None => continue
+        };
+        let mut extra: Vec<&(usize, String)> = file_cbfs.get(win).map(|v| v.iter().filter(|(n, _)| *n > ncbf).collect()).unwrap_or_default();
+        extra.sort_unstable();
+
+        let mut populated = Vec::new();
+        for (_, varname) in extra {
+            let var = _get_var(nch, varname)?;
+            let fill = _get_fill_value(&var);
+            if _get_record_data(&var, clargs)?.iter().any(|&v| !_is_fill_or_nan(v, fill) && v != 0.0) {
+                populated.push(varname.as_str());
+            }
+        }
+
+        if populated.is_empty() {
+            if clargs.verbosity >= 3 && !clargs.failures_only {
+                report!("    - PASS: {} has no continuum basis functions beyond its ncbf={}", win, ncbf);
+            }
+        }else{
+            all_ok = false;
+            if clargs.verbosity >= 2 {
+                report!("  - FAIL: {} fits ncbf={} continuum basis functions but {} have non-zero values", win, ncbf, populated.join(", "));
+            }
+        }
+    }
+
+    if clargs.verbosity >= 2 && all_ok && !clargs.failures_only {
+        report!("  - PASS: no window has more continuum basis functions populated than its ncbf");
+    }else if clargs.verbosity == 1 {
+        if all_ok {
+            if !clargs.failures_only{ report!("* PASS: No window has more continuum basis functions populated than its ncbf") };
+        }else{
+            report!("* FAIL: At least one window has more continuum basis functions populated than its ncbf in the windows table");
+        }
+    }
+
+    Ok(all_ok)
+}
+
+fn check_continuum_level(nch: &netcdf::File, clargs: &CmdLineArgs) -> Result<bool, CheckError> {
+    // A real spectrum has a positive continuum
+    let family = _window_family_vars(nch, "cl");
+    let results = _check_positive_family(nch, &family, clargs)?;
+    _print_window_family_summary("Continuum levels (cl)", "cl", &results, clargs);
+
+    Ok(results.all_ok)
+}
+
+fn check_airmass_values(nch: &netcdf::File, clargs: &CmdLineArgs) -> Result<bool, CheckError> {
+    if clargs.verbosity > 1 {
+        _print_header("\n=== Checking airmasses ===", clargs);
+    }
+
+    let family = _window_gas_family_vars(nch, "am");
+    let results = _check_positive_family(nch, &family, clargs)?;
+    _print_window_family_summary("Airmasses (am)", "am_<gas>", &results, clargs);
+
+    Ok(results.all_ok)
+}
+
+fn check_ovc(nch: &netcdf::File, clargs: &CmdLineArgs) -> Result<bool, CheckError> {
+    if clargs.verbosity > 1 {
+        _print_header("\n=== Checking overhead vertical columns ===", clargs);
+    }
+
+    let family = _window_gas_family_vars(nch, "ovc");
+    let results = _check_positive_family(nch, &family, clargs)?;
+    _print_window_family_summary("Overhead vertical columns (ovc)", "ovc_<gas>", &results, clargs);
+
+    if let Some(factor) = clargs.ovc_factor {
+        check_ovc_across_windows(nch, &family, factor, clargs)?;
+    }
+
+    Ok(results.all_ok)
+}
+
+fn check_ovc_across_windows(nch: &netcdf::File, family: &[(String, String)], factor: f32, clargs: &CmdLineArgs) -> Result<(), CheckError> {
+    // Different windows for the same gas see different parts of the spectrum, so their columns
+    // legitimately differ somewhat; this only notes large differences and never fails the check
+    let mut gas_windows: HashMap<&str, Vec<(&str, &str)>> = HashMap::new();
+    for (label, varname) in family.iter() {
+        if let Some((window, gas)) = label.rsplit_once('/') {
+            gas_windows.entry(gas).or_default().push((window, varname));
+        }
+    }
+    let mut gases: Vec<&str> = gas_windows.keys().copied().collect();
+    gases.sort_unstable();
+
+    for gas in gases {
+        let windows = &gas_windows[gas];
+        if windows.len() < 2 {
+            continue;
+        }
+
+        let mut all_data = Vec::new();
+        for (window, varname) in windows.iter() {
+            all_data.push((*window, _get_record_data(&_get_var(nch, varname)?, clargs)?));
+        }
+        let n_records = all_data[0].1.len();
+        if all_data.iter().any(|(_, v)| v.len() != n_records) {
+            if clargs.verbosity >= 1 {
+                report!("  NOTE: the {} ovc variables have different lengths, so cannot be compared across windows", gas);
+            }
+            continue;
+        }
+
+        let mut n_differ = 0;
+        let (mut worst_ratio, mut worst_low_win, mut worst_high_win) = (0.0, "", "");
+        for i in 0..n_records {
+            // Non-positive and NaN values are already failures of the positivity check, so are skipped here
+            let (mut low_win, mut low) = (all_data[0].0, f32::INFINITY);
+            let (mut high_win, mut high) = (all_data[0].0, 0.0);
+            for (window, values) in all_data.iter() {
+                if values[i] > 0.0 && values[i] < low {
+                    low_win = window;
+                    low = values[i];
+                }
+                if values[i] > high {
+                    high_win = window;
+                    high = values[i];
+                }
+            }
+
+            if low.is_finite() && high / low > factor {
+                n_differ += 1;
+                if high / low > worst_ratio {
+                    worst_ratio = high / low;
+                    worst_low_win = low_win;
+                    worst_high_win = high_win;
+                }
+            }
+        }
+
+        if n_differ > 0 && clargs.verbosity >= 1 {
+            report!("  NOTE: {} ovc differs by more than a factor of {} between windows in {}/{} records (at worst {:.2}x, {} vs. {})",
+                     gas, factor, n_differ, n_records, worst_ratio, worst_high_win, worst_low_win);
+        }
+    }
+
+    return Ok(());
+}
+
+fn check_vsf_errors(nch: &netcdf::File, clargs: &CmdLineArgs) -> Result<bool, CheckError> {
+    if clargs.verbosity > 1 {
+        _print_header("\n=== Checking scale factor errors ===", clargs);
+    }
+
+    // An error as large as the scale factor itself means the gas was not meaningfully retrieved
+    let frac = clargs.vsf_error_fraction;
+    let bounds = format!("[0, {} x |vsf|)", frac);
+    let family = _window_gas_family_vars(nch, "vsf");
+
+    let mut all_ok = true;
+    let mut worst: Option<(String, usize, usize)> = None;
+    for (window, varname) in family.iter() {
+        let err_varname = format!("{}_error", varname);
+        let err_var = match nch.variable(&err_varname) {
+            Some(v) => v,
+            None => {
+                if clargs.verbosity >= 2 {
+                    report!("  - FAIL: variable '{}' is not present but should be", err_varname);
+                }
+                all_ok = false;
+                continue;
+            }
+        };
+        let vsf_var = _get_var(nch, varname)?;
+        let vsf_fill = _get_fill_value(&vsf_var);
+        let err_fill = _get_fill_value(&err_var);
+        let vsf_data = _get_record_data(&vsf_var, clargs)?;
+        let err_data = _get_record_data(&err_var, clargs)?;
+        _check_same_length(varname, vsf_data.len(), &err_varname, err_data.len())?;
+
+        let mut n_valid: usize = 0;
+        let mut n_wrong: usize = 0;
+        for (&vsf, &err) in vsf_data.iter().zip(err_data.iter()) {
+            if _is_fill_or_nan(vsf, vsf_fill) || _is_fill_or_nan(err, err_fill) {
+                continue;
+            }
+            n_valid += 1;
+            if err < 0.0 || err >= frac * vsf.abs() {
+                n_wrong += 1;
+            }
+        }
+
+        all_ok = _print_bounded_results(&err_varname, n_valid, n_wrong, &bounds, clargs) && all_ok;
+        _update_worst_window(&mut worst, window, n_valid, n_wrong);
+    }
+
+    let results = WindowFamilyResults{all_ok: all_ok && !family.is_empty(), n_windows: family.len(), bounds: bounds, worst: worst};
+    _print_window_family_summary("Scale factor errors (vsf_<gas>_error)", "vsf_<gas>", &results, clargs);
+
+    Ok(results.all_ok)
+}
+
+fn check_vsf_cross_window(nch: &netcdf::File, clargs: &CmdLineArgs) -> Result<bool, CheckError> {
+    if clargs.verbosity > 1 {
+        _print_header("\n=== Checking scale factor consistency across windows ===", clargs);
+    }
+
+    // Each window fitting a gas retrieves its scale factor from the same observation, so they
+    // should agree to within their errors; group the <window>/<gas> family by gas to compare them
+    let factor = clargs.vsf_consistency_factor;
+    let mut gas_vars: HashMap<String, Vec<String>> = HashMap::new();
+    for (label, varname) in _window_gas_family_vars(nch, "vsf").into_iter() {
+        let gas = label.rsplit('/').next().unwrap().to_owned();
+        gas_vars.entry(gas).or_default().push(varname);
+    }
+    let mut gases: Vec<&String> = gas_vars.keys().filter(|g| gas_vars[*g].len() > 1).collect();
+    gases.sort_unstable();
+
+    if gases.is_empty() {
+        if clargs.verbosity >= 1 {
+            report!("* FAIL: no gas has <window>_vsf_<gas> variables in more than one window, so they cannot be compared");
+        }
+        return Ok(false);
+    }
+
+    let bounds = format!("{} x their combined errors of each other", factor);
+    let mut all_ok = true;
+    let mut worst: Option<(String, usize, usize)> = None;
+    for gas in gases {
+        let mut data: Vec<(Vec<f32>, Vec<f32>)> = Vec::new();
+        let mut first_varname: Option<&str> = None;
+        for varname in gas_vars[gas].iter() {
+            let err_varname = format!("{}_error", varname);
+            let err_var = match nch.variable(&err_varname) {
+                Some(v) => v,
+                None => {
+                    if clargs.verbosity >= 2 {
+                        report!("  - FAIL: variable '{}' is not present but should be", err_varname);
+                    }
+                    all_ok = false;
+                    continue;
+                }
+            };
+            let vsf_var = _get_var(nch, varname)?;
+            let (vsf_fill, err_fill) = (_get_fill_value(&vsf_var), _get_fill_value(&err_var));
+            let values = _get_record_data(&vsf_var, clargs)?;
+            let errors = _get_record_data(&err_var, clargs)?;
+            _check_same_length(varname, values.len(), &err_varname, errors.len())?;
+            if let (Some(first), Some((first_values, _))) = (first_varname, data.first()) {
+                _check_same_length(first, first_values.len(), varname, values.len())?;
+            }else{
+                first_varname = Some(varname);
+            }
+            // Fills become NaNs so every comparison with them is skipped
+            let values = values.into_iter().map(|v| if _is_fill_or_nan(v, vsf_fill) {f32::NAN} else {v}).collect();
+            let errors = errors.into_iter().map(|e| if _is_fill_or_nan(e, err_fill) {f32::NAN} else {e}).collect();
+            data.push((values, errors));
+        }
+
+        if data.len() < 2 {
+            continue;
+        }
+        let n_records = data[0].0.len();
+        let mut n_valid = 0;
+        let mut n_wrong = 0;
+        for i in 0..n_records {
+            let mut any_valid = false;
+            let mut disagree = false;
+            for a in 0..data.len() {
+                for b in a+1..data.len() {
+                    let diff = (data[a].0[i] - data[b].0[i]).abs();
+                    let combined_err = (data[a].1[i].powi(2) + data[b].1[i].powi(2)).sqrt();
+                    if diff.is_nan() || combined_err.is_nan() {
+                        continue;
+                    }
+                    any_valid = true;
+                    disagree = disagree || diff > factor * combined_err;
+                }
+            }
+            if any_valid {
+                n_valid += 1;
+            }
+            if disagree {
+                n_wrong += 1;
+            }
+        }
+
+        let desc = format!("vsf_{} ({} windows)", gas, data.len());
+        all_ok = _print_bounded_results(&desc, n_valid, n_wrong, &bounds, clargs) && all_ok;
+        _update_worst_window(&mut worst, gas, n_valid, n_wrong);
+    }
+
+    if let Some((gas, n_total, n_wrong)) = &worst {
+        if clargs.verbosity >= 2 {
+            report!("  - worst gas is {} ({}/{} records disagree between windows)", gas, n_wrong, n_total);
+        }else if clargs.verbosity == 1 {
+            report!("* FAIL: Scale factors disagree between windows by more than {} x their combined errors (worst is {}, {}/{} records)", factor, gas, n_wrong, n_total);
+        }
+    }else if clargs.verbosity == 1 {
+        if all_ok {
+            if !clargs.failures_only{ report!("* PASS: Scale factors of each gas agree between windows to within {} x their combined errors", factor) };
+        }else{
+            report!("* FAIL: At least one <window>_vsf_<gas>_error is missing, so its scale factor could not be compared");
+        }
+    }
+
+    Ok(all_ok)
+}
+
+fn check_zpres_consistency(nch: &netcdf::File, clargs: &CmdLineArgs) -> Result<bool, CheckError> {
+    if clargs.verbosity > 1 {
+        _print_header("\n=== Checking zpres consistency across windows ===", clargs);
+    }
+
+    let family = _window_family_vars(nch, "zpres");
+    if family.len() < 2 {
+        if clargs.verbosity >= 1 {
+            report!("* FAIL: fewer than two <window>_zpres variables are present, so they cannot be compared");
+        }
+        return Ok(false);
+    }
+
+    let mut all_data = Vec::new();
+    for (window, varname) in family.iter() {
+        let values = _get_record_data(&_get_var(nch, varname)?, clargs)?;
+        all_data.push((window, values));
+    }
+    let first_var = _get_var(nch, &family[0].1)?;
+
+    let n_records = all_data[0].1.len();
+    if let Some((window, values)) = all_data.iter().find(|(_, v)| v.len() != n_records) {
+        if clargs.verbosity >= 1 {
+            report!("* FAIL: {}_zpres has {} values, but {}_zpres has {}", window, values.len(), all_data[0].0, n_records);
+        }
+        return Ok(false);
+    }
+
+    // zpres is the same surface pressure for every window in an observation, so any
+    // disagreement beyond rounding means records from different spectra were collated together
+    let mut n_wrong = 0;
+    for i in 0..n_records {
+        // NaNs fail both comparisons, so are skipped
+        let (mut low_win, mut low) = (all_data[0].0, f32::INFINITY);
+        let (mut high_win, mut high) = (all_data[0].0, f32::NEG_INFINITY);
+        for (window, values) in all_data.iter() {
+            if values[i] < low {
+                low_win = window;
+                low = values[i];
+            }
+            if values[i] > high {
+                high_win = window;
+                high = values[i];
+            }
+        }
+
+        if low <= high {
+            if high - low > clargs.zpres_tolerance {
+                n_wrong += 1;
+                if clargs.verbosity >= 3 && n_wrong <= 10 {
+                    report!("    - record {}: {}_zpres = {} but {}_zpres = {}", _file_record_index(&first_var, i, clargs), low_win, low, high_win, high);
+                }else if clargs.verbosity >= 3 && n_wrong == 11 {
+                    report!("    (further inconsistent records omitted)");
+                }
+            }
+        }
+    }
+
+    let all_ok = n_wrong == 0;
+    if clargs.verbosity >= 2 {
+        if all_ok {
+            if !clargs.failures_only{ report!("  - PASS: zpres agrees to within {} across all {} windows", clargs.zpres_tolerance, family.len()) };
+        }else{
+            report!("  - FAIL: {}/{} records have zpres differing by more than {} between windows", n_wrong, n_records, clargs.zpres_tolerance);
+        }
+    }else if clargs.verbosity == 1 {
+        if all_ok {
+            if !clargs.failures_only{ report!("* PASS: zpres is consistent across windows") };
+        }else{
+            report!("* FAIL: zpres is inconsistent across windows in {}/{} records", n_wrong, n_records);
+        }
+    }
+
+    let pout_ok = check_zpres_vs_pout(nch, &all_data, clargs)?;
+    Ok(all_ok && pout_ok)
+}
+
+fn check_zpres_vs_pout(nch: &netcdf::File, all_data: &[(&String, Vec<f32>)], clargs: &CmdLineArgs) -> Result<bool, CheckError> {
+    // zpres is the pressure at the bottom of the fit's pressure grid, so should be within the
+    // near-surface layer of the measured surface pressure; a large offset points to a pressure grid bug
+    let pout_var = match nch.variable("pout") {
+        Some(v) => v,
+        None => {
+            if clargs.verbosity >= 2 {
+                report!("  - FAIL: variable 'pout' is not present but should be");
+            }else if clargs.verbosity == 1 {
+                report!("* FAIL: pout is missing, so zpres cannot be compared to it");
+            }
+            return Ok(false);
+        }
+    };
+    let pout = _get_record_data(&pout_var, clargs)?;
+    let pout_fill = _get_fill_value(&pout_var);
+    if pout.len() != all_data[0].1.len() {
+        if clargs.verbosity >= 1 {
+            report!("* FAIL: pout has {} values, but {}_zpres has {}", pout.len(), all_data[0].0, all_data[0].1.len());
+        }
+        return Ok(false);
+    }
+
+    let mut n_valid = 0;
+    let mut n_wrong = 0;
+    let mut max_diff: f32 = 0.0;
+    for (i, &p) in pout.iter().enumerate() {
+        let mut zpres: Vec<f32> = all_data.iter().map(|(_, v)| v[i]).filter(|z| !z.is_nan()).collect();
+        if zpres.is_empty() || _is_fill_or_nan(p, pout_fill) {
+            continue;
+        }
+        zpres.sort_unstable_by(|a, b| a.total_cmp(b));
+        let median = zpres[zpres.len() / 2];
+
+        n_valid += 1;
+        let diff = (median - p).abs();
+        max_diff = max_diff.max(diff);
+        if diff > clargs.zpres_pout_tolerance {
+            n_wrong += 1;
+            if clargs.verbosity >= 3 && n_wrong <= 10 {
+                report!("    - record {}: median zpres = {} but pout = {}", _file_record_index(&pout_var, i, clargs), median, p);
+            }else if clargs.verbosity >= 3 && n_wrong == 11 {
+                report!("    (further records omitted)");
+            }
+        }
+    }
+
+    let bounds = format!("{} of pout", clargs.zpres_pout_tolerance);
+    let all_ok = _print_bounded_results("median zpres", n_valid, n_wrong, &bounds, clargs);
+    if clargs.verbosity == 1 {
+        if all_ok {
+            if !clargs.failures_only{ report!("* PASS: The median zpres across windows is within {} of pout", clargs.zpres_pout_tolerance) };
+        }else{
+            report!("* FAIL: The median zpres across windows differs from pout by more than {} (at most {})", clargs.zpres_pout_tolerance, max_diff);
+        }
+    }
+
+    Ok(all_ok)
+}
+
+// ************ //
+// CUSTOM RULES //
+// ************ //
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+enum RuleComparison {
+    Equals,
+    InRange,
+    Present,
+    Absent
+}
+
+#[derive(Debug, Deserialize)]
+struct CustomRule {
+    variable: String,
+    comparison: RuleComparison,
+    value: Option<f32>,
+    min: Option<f32>,
+    max: Option<f32>
+}
+
+#[derive(Debug, Deserialize)]
+struct RulesFile {
+    #[serde(default)]
+    rule: Vec<CustomRule>
+}
+
+fn read_rules_file(rules_file: &str) -> Result<Vec<CustomRule>, CheckError> {
+    let contents = match std::fs::read_to_string(rules_file) {
+        Ok(s) => s,
+        Err(err) => return Err(CheckError::FileRead(String::from(rules_file), err.to_string()))
+    };
+
+    let parsed: RulesFile = match toml::from_str(&contents) {
+        Ok(r) => r,
+        Err(err) => return Err(CheckError::Other(format!("Unable to parse rules file {}: {}", rules_file, err)))
+    };
+
+    // Catch rules missing the values their comparison needs now, rather than
+    // partway through checking the file
+    for (i, rule) in parsed.rule.iter().enumerate() {
+        match rule.comparison {
+            RuleComparison::Equals if rule.value.is_none() => {
+                return Err(CheckError::Other(format!("Rule #{} in {} (variable '{}') uses 'equals' but has no 'value'", i+1, rules_file, rule.variable)));
+            },
+            RuleComparison::InRange if rule.min.is_none() && rule.max.is_none() => {
+                return Err(CheckError::Other(format!("Rule #{} in {} (variable '{}') uses 'in-range' but has neither 'min' nor 'max'", i+1, rules_file, rule.variable)));
+            },
+            _ => {}
+        }
+    }
+
+    return Ok(parsed.rule);
+}
+
+fn _describe_rule(rule: &CustomRule) -> String {
+    match rule.comparison {
+        RuleComparison::Equals => format!("'{}' equals {}", rule.variable, rule.value.unwrap()),
+        RuleComparison::InRange => {
+            let lo = rule.min.map_or(String::from("-inf"), |v| v.to_string());
+            let hi = rule.max.map_or(String::from("+inf"), |v| v.to_string());
+            format!("'{}' is within [{}, {}]", rule.variable, lo, hi)
+        },
+        RuleComparison::Present => format!("'{}' is present", rule.variable),
+        RuleComparison::Absent => format!("'{}' is absent", rule.variable)
+    }
+}
+
+fn run_custom_rules(nch: &netcdf::File, rules: &[CustomRule], clargs: &CmdLineArgs) -> Result<bool, CheckError> {
+    if clargs.verbosity > 1 {
+        _print_header("\n=== Checking custom rules ===", clargs);
+    }
+
+    let mut all_ok = true;
+    for rule in rules {
+        let desc = _describe_rule(rule);
+        if clargs.verbosity > 2 {
+            report!("  * Checking rule {}:", desc);
+        }
+
+        let rule_ok = match rule.comparison {
+            RuleComparison::Equals => _check_float_variable(nch, &rule.variable, rule.value.unwrap(), true, clargs)?,
+            RuleComparison::InRange => _check_float_range_variable(nch, &rule.variable, rule.min, rule.max, true, clargs)?,
+            RuleComparison::Present => check_variables_present(nch, std::slice::from_ref(&rule.variable), true, clargs)?,
+            RuleComparison::Absent => check_variables_present(nch, std::slice::from_ref(&rule.variable), false, clargs)?
+        };
+        all_ok = all_ok && rule_ok;
+
+        // Each rule is its own item, so it gets a line at the category level.
+        // check_variables_present already prints a line per variable at verbosity 2,
+        // so only the value comparisons need one here.
+        let is_value_rule = matches!(rule.comparison, RuleComparison::Equals | RuleComparison::InRange);
+        if clargs.verbosity == 1 {
+            if rule_ok {
+                if !clargs.failures_only{ report!("* PASS: Custom rule {}", desc) };
+            }else{
+                report!("* FAIL: Custom rule {} does not hold", desc);
+            }
+        }else if clargs.verbosity == 2 && is_value_rule {
+            if rule_ok {
+                if !clargs.failures_only{ report!("  - PASS: rule {}", desc) };
+            }else{
+                report!("  - FAIL: rule {} does not hold", desc);
+            }
+        }
+    }
+
+    Ok(all_ok)
+}
+
+
+// ****** //
+// SCHEMA //
+// ****** //
+
+#[derive(Debug, Deserialize)]
+struct SchemaVariable {
+    name: String,
+    units: Option<String>,
+    #[serde(rename = "type")]
+    var_type: Option<String>
+}
+
+#[derive(Debug, Deserialize)]
+struct SchemaAttribute {
+    name: String,
+    value: Option<String>,
+    pattern: Option<String>
+}
+
+#[derive(Debug, Deserialize)]
+struct Schema {
+    #[serde(default)]
+    variables: Vec<SchemaVariable>,
+    #[serde(default)]
+    attributes: Vec<SchemaAttribute>
+}
+
+fn read_schema_file(schema_file: &str) -> Result<Schema, CheckError> {
+    let contents = match std::fs::read_to_string(schema_file) {
+        Ok(s) => s,
+        Err(err) => return Err(CheckError::FileRead(String::from(schema_file), err.to_string()))
+    };
+
+    let schema: Schema = match serde_json::from_str(&contents) {
+        Ok(s) => s,
+        Err(err) => return Err(CheckError::Other(format!("Unable to parse schema file {}: {}", schema_file, err)))
+    };
+
+    // Like the rules file, catch mistakes now rather than partway through checking the file
+    for att in schema.attributes.iter() {
+        if att.value.is_none() && att.pattern.is_none() {
+            return Err(CheckError::Other(format!("Attribute '{}' in {} has neither a 'value' nor a 'pattern'", att.name, schema_file)));
+        }
+        if let Some(pattern) = &att.pattern {
+            if let Err(err) = Regex::new(pattern) {
+                return Err(CheckError::Other(format!("Attribute '{}' in {} has an invalid pattern: {}", att.name, schema_file, err)));
+            }
+        }
+    }
+
+    return Ok(schema);
+}
+
+fn _schema_type_name(type_name: &str) -> &str {
+    // netCDF's own names for the common types, in addition to the Rust names the netcdf crate uses
+    match type_name {
+        "byte" => "i8",
+        "ubyte" => "u8",
+        "short" => "i16",
+        "ushort" => "u16",
+        "int" => "i32",
+        "uint" => "u32",
+        "int64" => "i64",
+        "uint64" => "u64",
+        "float" => "f32",
+        "double" => "f64",
+        other => other
+    }
+}
+
+fn run_schema(nch: &netcdf::File, schema: &Schema, clargs: &CmdLineArgs) -> Result<bool, CheckError> {
+    if clargs.verbosity > 1 {
+        _print_header("\n=== Checking schema variables ===", clargs);
+    }
+
+    let names: Vec<String> = schema.variables.iter().map(|v| v.name.clone()).collect();
+    let present_ok = check_variables_present(nch, &names, true, clargs)?;
+
+    let mut types_ok = true;
+    for schema_var in schema.variables.iter() {
+        let var = match nch.variable(&schema_var.name) {
+            Some(v) => v,
+            None => continue
+        };
+
+        if let Some(expected) = &schema_var.var_type {
+            let actual = var.vartype().name();
+            if actual != _schema_type_name(expected) {
+                types_ok = false;
+                if clargs.verbosity >= 2 {
+                    report!("  - FAIL: variable '{}' has type {}, expected {}", schema_var.name, actual, expected);
+                }
+            }
+        }
+
+        if let Some(expected) = &schema_var.units {
+            let actual = _get_units(&var);
+            if actual.as_deref() != Some(expected.as_str()) {
+                types_ok = false;
+                if clargs.verbosity >= 2 {
+                    report!("  - FAIL: variable '{}' has units '{}', expected '{}'", schema_var.name, actual.as_deref().unwrap_or("(none)"), expected);
+                }
+            }
+        }
+    }
+
+    if clargs.verbosity > 1 {
+        _print_header("\n=== Checking schema attributes ===", clargs);
+    }
+
+    let mut atts_ok = true;
+    for att in schema.attributes.iter() {
+        let att_ok = if let Some(expected) = &att.value {
+            _check_string_attribute_value(nch, &att.name, expected, clargs)?
+        }else{
+            // read_schema_file already checked that there is a pattern and that it compiles
+            let re = Regex::new(att.pattern.as_ref().unwrap()).unwrap();
+            let att_val = _get_string_attribute_value(nch, &att.name, clargs)?;
+            if att_val == ATT_MISSING_STR {
+                // _get_string_attribute_value already reported it
+                false
+            }else if re.is_match(&att_val) {
+                if clargs.verbosity >= 2 && !clargs.failures_only {
+                    report!("  - PASS: attribute '{}' matches the expected pattern", att.name);
+                }
+                true
+            }else{
+                if clargs.verbosity >= 2 {
+                    report!("  - FAIL: attribute '{}' does not match the pattern '{}' (value = '{}')", att.name, re.as_str(), att_val);
+                }
+                false
+            }
+        };
+        atts_ok = atts_ok && att_ok;
+    }
+
+    if clargs.verbosity == 1 {
+        if present_ok {
+            if !clargs.failures_only{ report!("* PASS: All schema variables are present") };
+        }else{
+            report!("* FAIL: At least one schema variable is missing");
+        }
+
+        if types_ok {
+            if !clargs.failures_only{ report!("* PASS: All schema variables have the expected types and units") };
+        }else{
+            report!("* FAIL: At least one schema variable has the wrong type or units");
+        }
+
+        if atts_ok {
+            if !clargs.failures_only{ report!("* PASS: All schema attributes have the expected values") };
+        }else{
+            report!("* FAIL: At least one schema attribute is missing or has the wrong value");
+        }
+    }
+
+    Ok(present_ok && types_ok && atts_ok)
+}
+
+// The test categories driver reports (e.g. to --sqlite and --prometheus) with what each checks, in the
+// order they run; the optional ones only run when given their --check-* flag or --rules
+const CATEGORIES: [(&'static str, &'static str); 38] = [
+    ("adcfs", "ADCFs, their errors, g, and p match the ADCF table"),
+    ("aicfs", "AICFs and their errors match the AICF table"),
+    ("aicf_adcf", "Each AICF gas has the ADCFs of its windows"),
+    ("window_sfs", "Window-to-window scale factors match the windows table"),
+    ("windows", "The expected windows are present and the removed ones absent"),
+    ("versions", "The program version attributes are the GGG2020 Phase 2 ones"),
+    ("ingaas_vars", "All expected InGaAs variables are present"),
+    ("o2_window", "The O2 7885 airmass window is present"),
+    ("cf", "(--check-cf) Channel fringe variables match the windows that fit them"),
+    ("zo", "(--check-zo) Zero-level offsets match the windows that fit them and are small"),
+    ("sg", "(--check-sg) Solar-gas stretches of the windows that fit them are near --sg-expected"),
+    ("error_prop", "(--check-error-prop) x<gas>_error is ada_x<gas>_error scaled by the AICF"),
+    ("hf", "(--check-hf) xhf is not implausibly high for the latitude; only a note unless --hf-fail"),
+    ("error_pairing", "(--check-error-pairing) Variables and their errors come in pairs"),
+    ("priors", "(--check-priors) Prior variables are complete and plausible"),
+    ("checksums", "(--check-checksums) windows_checksum and apriori_checksum are consistent"),
+    ("lse", "(--check-lse) Laser sampling errors are small and consistent with lsf"),
+    ("ak", "(--check-ak) Averaging kernel variables have the expected structure"),
+    ("gravity", "(--check-gravity) prior_gravity matches the gravity at the prior latitude and altitudes"),
+    ("h2o_dmf", "(--check-h2o-dmf) Measured and model H2O dry mole fractions agree"),
+    ("met", "(--check-met) Meteorology variables are present and plausible"),
+    ("pointing", "(--check-pointing) Pointing diagnostics are within bounds"),
+    ("fs", "(--check-fs) Window frequency shifts are within bounds"),
+    ("rms", "(--check-rms) Window fit residuals are finite and small"),
+    ("nit", "(--check-nit) No window fit reached the maximum number of iterations"),
+    ("continuum", "(--check-continuum) Window continuum fits are plausible"),
+    ("airmass", "(--check-airmass) Window airmasses are positive and finite"),
+    ("ovc", "(--check-ovc) Window overhead vertical columns are positive and finite"),
+    ("vsf_error", "(--check-vsf-error) Scale factor errors are smaller than the scale factors"),
+    ("vsf_consistency", "(--check-vsf-consistency) A gas's scale factors agree between windows"),
+    ("time", "(--check-time) Record times are between --earliest-time and now"),
+    ("zpres", "(--check-zpres) zpres agrees between windows and with pout"),
+    ("instrument", "(--check-instrument) graw is consistent with opd"),
+    ("sia", "(--check-sia) Solar intensity diagnostics are within bounds"),
+    ("geo", "(--check-geo) The solar azimuth is within range"),
+    ("o2", "(--check-o2) xo2 and the O2 columns are consistent"),
+    ("cells", "(--check-cells) Gas cell variables are consistent with cell_index"),
+    ("rules", "(--rules) The custom checks in the rules file pass"),
+];
+
+fn list_categories() {
+    let width = CATEGORIES.iter().map(|(name, _)| name.len()).max().unwrap_or(0);
+    for (name, description) in CATEGORIES.iter() {
+        println!("{:width$}  {}", name, description, width = width);
+    }
+}
+
+fn read_hash_file(hash_file: &str) -> Result<HashMap<String, String>, CheckError> {
+    // Same layout as sha256sum output: "<digest>  <path>", with an optional '*' marking binary mode
+    let contents = match std::fs::read_to_string(hash_file) {
+        Ok(s) => s,
+        Err(err) => return Err(CheckError::FileRead(String::from(hash_file), err.to_string()))
+    };
+
+    let mut digests = HashMap::new();
+    for line in contents.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let (digest, path) = match line.find(char::is_whitespace) {
+            Some(i) => (&line[..i], line[i..].trim_start().trim_start_matches('*')),
+            None => return Err(CheckError::TableParse(String::from(line), String::from("expected a digest and a path")))
+        };
+        if digest.len() != 64 || !digest.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(CheckError::TableParse(String::from(line), String::from("digest is not a SHA256 hex string")));
+        }
+        digests.insert(path.to_owned(), digest.to_ascii_lowercase());
+    }
+
+    return Ok(digests);
+}
+
+fn _sha256_file(path: &str) -> Result<String, CheckError> {
+    use std::io::Read;
+    use sha2::{Digest,Sha256};
+
+    // Read in chunks so that large files need not fit in memory
+    let mut file = match std::fs::File::open(path) {
+        Ok(f) => f,
+        Err(err) => return Err(CheckError::FileOpen(String::from(path), err.to_string()))
+    };
+    let mut hasher = Sha256::new();
+    let mut buf = vec![0u8; 1 << 20];
+    loop {
+        let n = match file.read(&mut buf) {
+            Ok(0) => break,
+            Ok(n) => n,
+            Err(err) => return Err(CheckError::FileRead(String::from(path), err.to_string()))
+        };
+        hasher.update(&buf[..n]);
+    }
+
+    return Ok(format!("{:x}", hasher.finalize()));
+}
+
+fn verify_file_hash(nc_file: &str, hash_file: &str, clargs: &CmdLineArgs) -> Result<(), CheckError> {
+    let digests = read_hash_file(hash_file)?;
+
+    // Match on the path as given first, then on the bare file name
+    let file_name = std::path::Path::new(nc_file).file_name().and_then(|n| n.to_str()).unwrap_or(nc_file);
+    let expected = match digests.get(nc_file).or_else(|| digests.get(file_name)) {
+        Some(d) => d,
+        None => return Err(CheckError::Other(format!("{} is not listed in hash file {}", nc_file, hash_file)))
+    };
+
+    let actual = _sha256_file(nc_file)?;
+    if &actual != expected {
+        return Err(CheckError::Other(format!("SHA256 of {} is {}, but {} expects {} - the file may be truncated or corrupted", nc_file, actual, hash_file, expected)));
+    }
+
+    if clargs.verbosity > 1 {
+        println!("  NOTE: SHA256 of {} matches {}", nc_file, hash_file);
+    }
+    Ok(())
+}
+
+fn driver(nc_file: &str, clargs: &CmdLineArgs) -> Result<bool, CheckError> {
+    if let Some(hash_file) = &clargs.hash_file {
+        verify_file_hash(nc_file, hash_file, clargs)?;
+    }
+    if clargs.attrs_only {
+        return attrs_only_driver(nc_file, clargs);
+    }
+    if let Some(schema_file) = &clargs.schema_file {
+        return schema_driver(nc_file, schema_file, clargs);
+    }
+    if clargs.encoding_report {
+        return encoding_report_driver(nc_file, clargs);
+    }
+    if let Some(varname) = &clargs.dump_failing_indices {
+        return dump_failing_indices_driver(nc_file, varname, clargs);
+    }
+    
+    let (adcfs, aicfs) = load_correction_tables(clargs);
+    let (windows, skipped_windows) = read_windows_table(clargs.release);
+    let rules = match &clargs.rules_file {
+        Some(f) => read_rules_file(f)?,
+        None => Vec::new()
+    };
+
+    let nch = match netcdf::open(nc_file) {
+        Ok(h) => h,
+        Err(err) => return Err(CheckError::FileOpen(String::from(nc_file), err.to_string()))
+    };
+
+    // The record mask depends on each file's times, so --since needs a per-file copy of the options
+    let masked_clargs;
+    let clargs = match &clargs.since {
+        Some(since) => {
+            let mask = _compute_record_mask(&nch, since, clargs)?;
+            if clargs.verbosity >= 1 {
+                println!("NOTE: only checking the {} of {} records at or after {}", mask.iter().filter(|&&keep| keep).count(), mask.len(), since);
+            }
+            masked_clargs = CmdLineArgs{record_mask: Some(mask), ..clargs.clone()};
+            &masked_clargs
+        },
+        None => clargs
+    };
+
+    if let Some(n) = clargs.first_n {
+        if clargs.verbosity >= 1 {
+            println!("NOTE: only checking the first {} records of each variable, so failures in later records will be missed", n);
+        }
+    }
+
+    // --compact-fail replaces all the usual check output with one line per failing category,
+    // so run the checks quietly but keep the caller's options for the overall result line
+    let report_clargs = clargs;
+    let quiet_clargs;
+    let clargs = if clargs.compact_fail {
+        quiet_clargs = CmdLineArgs{verbosity: -1, ..clargs.clone()};
+        &quiet_clargs
+    }else{
+        clargs
+    };
+
+    let adcfs_ok = check_adcfs(&nch, &adcfs, clargs)?;
+    let aicfs_ok = check_aicfs(&nch, &aicfs, clargs)?;
+    let aicf_adcf_ok = check_aicf_adcf_coverage(&nch, &adcfs, &aicfs, clargs);
+    let sfs_ok = check_window_scale_factors(&nch, &windows, clargs)?;
+    let file_vars = _get_variable_names(&nch);
+    let windows_ok = check_included_windows(&file_vars, &windows, &skipped_windows, clargs)?;
+    let versions_ok = check_program_versions(&nch, clargs)?;
+    let ingaas_ok = check_ingaas_variables(&file_vars, clargs)?;
+    let o2_window_ok = check_o2_airmass_window(&file_vars, clargs);
+    let cf_ok = if clargs.check_cf {check_channel_fringe(&file_vars, &windows, clargs)?} else {true};
+    let zo_ok = if clargs.check_zo {check_zero_offset(&nch, &windows, clargs)?} else {true};
+    let sg_ok = if clargs.check_sg {check_solar_gas_stretch(&nch, &windows, clargs)?} else {true};
+    let error_prop_ok = if clargs.check_error_prop {check_error_propagation(&nch, &aicfs, clargs)?} else {true};
+    let error_pairs_ok = if clargs.check_error_pairing {check_error_pairing(&file_vars, clargs)?} else {true};
+    let priors_ok = if clargs.check_priors {check_priors(&nch, clargs)?} else {true};
+    let checksums_ok = if clargs.check_checksums {check_checksums(&nch, clargs)?} else {true};
+    let lse_ok = if clargs.check_lse {check_laser_sampling(&nch, clargs)?} else {true};
+    let ak_ok = if clargs.check_ak {check_averaging_kernels(&nch, clargs)?} else {true};
+    let gravity_ok = if clargs.check_gravity {check_gravity(&nch, clargs)?} else {true};
+    let h2o_dmf_ok = if clargs.check_h2o_dmf {check_h2o_dmf(&nch, clargs)?} else {true};
+    let met_ok = if clargs.check_met {check_met(&nch, clargs)?} else {true};
+    let pointing_ok = if clargs.check_pointing {check_pointing(&nch, clargs)?} else {true};
+    let fs_ok = if clargs.check_fs {check_freq_shifts(&nch, clargs)?} else {true};
+    let rms_ok = if clargs.check_rms {check_rmsocl(&nch, clargs)?} else {true};
+    let nit_ok = if clargs.check_nit {check_iterations(&nch, clargs)?} else {true};
+    let continuum_ok = if clargs.check_continuum {check_continuum(&nch, &windows, clargs)?} else {true};
+    let vsf_error_ok = if clargs.check_vsf_error {check_vsf_errors(&nch, clargs)?} else {true};
+    let ovc_ok = if clargs.check_ovc {check_ovc(&nch, clargs)?} else {true};
+    let airmass_ok = if clargs.check_airmass {check_airmass_values(&nch, clargs)?} else {true};
+    let vsf_consistency_ok = if clargs.check_vsf_consistency {check_vsf_cross_window(&nch, clargs)?} else {true};
+    let time_ok = if clargs.check_time {check_time_range(&nch, clargs)?} else {true};
+    let zpres_ok = if clargs.check_zpres {check_zpres_consistency(&nch, clargs)?} else {true};
+    let instrument_ok = if clargs.check_instrument {check_graw_opd(&nch, clargs)?} else {true};
+    let sia_ok = if clargs.check_sia {check_solar_intensity(&nch, clargs)?} else {true};
+    let geo_ok = if clargs.check_geo {check_geolocation(&nch, clargs)?} else {true};
+    let hf_ok = if clargs.check_hf {check_hf_sanity(&nch, clargs)?} else {true};
+    let o2_ok = if clargs.check_o2 {check_o2_consistency(&nch, clargs)?} else {true};
+    let cells_ok = if clargs.check_cells {check_cell_block(&nch, clargs)?} else {true};
+    let rules_ok = if rules.is_empty() {true} else {run_custom_rules(&nch, &rules, clargs)?};
+
+    let overall_ok = adcfs_ok && aicfs_ok && aicf_adcf_ok && sfs_ok && windows_ok && versions_ok && ingaas_ok && o2_window_ok
+        && cf_ok && zo_ok && sg_ok && error_prop_ok && hf_ok && error_pairs_ok && priors_ok && checksums_ok && lse_ok && ak_ok && gravity_ok && h2o_dmf_ok && met_ok && pointing_ok && fs_ok && rms_ok && nit_ok && continuum_ok && airmass_ok && ovc_ok && vsf_error_ok && vsf_consistency_ok && time_ok && zpres_ok && instrument_ok && sia_ok && geo_ok && o2_ok && cells_ok && rules_ok;
+
+    // Each is (category, whether it was run, whether it passed)
+    let categories = [
+        ("adcfs", true, adcfs_ok),
+        ("aicfs", true, aicfs_ok),
+        ("aicf_adcf", true, aicf_adcf_ok),
+        ("window_sfs", true, sfs_ok),
+        ("windows", true, windows_ok),
+        ("versions", true, versions_ok),
+        ("ingaas_vars", true, ingaas_ok),
+        ("o2_window", true, o2_window_ok),
+        ("cf", clargs.check_cf, cf_ok),
+        ("zo", clargs.check_zo, zo_ok),
+        ("sg", clargs.check_sg, sg_ok),
+        ("error_prop", clargs.check_error_prop, error_prop_ok),
+        ("hf", clargs.check_hf, hf_ok),
+        ("error_pairing", clargs.check_error_pairing, error_pairs_ok),
+        ("priors", clargs.check_priors, priors_ok),
+        ("checksums", clargs.check_checksums, checksums_ok),
+        ("lse", clargs.check_lse, lse_ok),
+        ("ak", clargs.check_ak, ak_ok),
+        ("gravity", clargs.check_gravity, gravity_ok),
+        ("h2o_dmf", clargs.check_h2o_dmf, h2o_dmf_ok),
+        ("met", clargs.check_met, met_ok),
+        ("pointing", clargs.check_pointing, pointing_ok),
+        ("fs", clargs.check_fs, fs_ok),
+        ("rms", clargs.check_rms, rms_ok),
+        ("nit", clargs.check_nit, nit_ok),
+        ("continuum", clargs.check_continuum, continuum_ok),
+        ("airmass", clargs.check_airmass, airmass_ok),
+        ("ovc", clargs.check_ovc, ovc_ok),
+        ("vsf_error", clargs.check_vsf_error, vsf_error_ok),
+        ("vsf_consistency", clargs.check_vsf_consistency, vsf_consistency_ok),
+        ("time", clargs.check_time, time_ok),
+        ("zpres", clargs.check_zpres, zpres_ok),
+        ("instrument", clargs.check_instrument, instrument_ok),
+        ("sia", clargs.check_sia, sia_ok),
+        ("geo", clargs.check_geo, geo_ok),
+        ("o2", clargs.check_o2, o2_ok),
+        ("cells", clargs.check_cells, cells_ok),
+        ("rules", !rules.is_empty(), rules_ok),
+    ];
+
+    if clargs.compact_fail {
+        // The table-driven categories print their own line with how many windows or gases failed
+        for (category, ran, ok) in categories.iter() {
+            if *ran && !*ok && !["adcfs", "aicfs", "window_sfs"].contains(category) {
+                println!("FAIL {}", category);
+            }
+        }
+    }
+
+    if let Some(db_file) = &clargs.sqlite_file {
+        record_validation(db_file, nc_file, overall_ok, &categories)?;
+    }
+    if let Some(prom_file) = &clargs.prometheus_file {
+        record_prometheus_metrics(prom_file, nc_file, overall_ok, &categories)?;
+    }
+
+    print_overall_result(nc_file, overall_ok, &categories, report_clargs);
+    
+    return Ok(overall_ok);
+}
+
+fn attrs_only_driver(nc_file: &str, clargs: &CmdLineArgs) -> Result<bool, CheckError> {
+    // Opening only reads the header, so skipping every check that reads
+    // variable data makes this very fast
+    let nch = match netcdf::open(nc_file) {
+        Ok(h) => h,
+        Err(err) => return Err(CheckError::FileOpen(String::from(nc_file), err.to_string()))
+    };
+
+    let versions_ok = check_program_versions(&nch, clargs)?;
+    print_overall_result(nc_file, versions_ok, &[("versions", true, versions_ok)], clargs);
+
+    return Ok(versions_ok);
+}
+
+fn encoding_report_driver(nc_file: &str, clargs: &CmdLineArgs) -> Result<bool, CheckError> {
+    // Only prints how the expected variables are stored; nothing is checked, so this always passes
+    let nch = match netcdf::open(nc_file) {
+        Ok(h) => h,
+        Err(err) => return Err(CheckError::FileOpen(String::from(nc_file), err.to_string()))
+    };
+
+    let mut n_missing = 0;
+    for varname in EXPECTED_INGAAS_VARS.split(',') {
+        let var = match nch.variable(varname) {
+            Some(v) => v,
+            None => {
+                n_missing += 1;
+                continue;
+            }
+        };
+        let dims: Vec<String> = var.dimensions().iter().map(|d| format!("{}={}", d.name(), d.len())).collect();
+        println!("{}: {} [{}]", varname, var.vartype().name(), dims.join(", "));
+    }
+
+    if n_missing > 0 && clargs.verbosity >= 0 {
+        println!("({} expected variables are not present)", n_missing);
+    }
+    return Ok(true);
+}
+
+fn _expected_table_value(varname: &str, adcfs: &HashMap<String, Adcf>, aicfs: &HashMap<String, Aicf>, windows: &HashMap<String, Window>, clargs: &CmdLineArgs) -> Option<f32> {
+    // The value the ADCF, AICF, or window scale factor checks expect this variable to have
+    if let Some(win) = varname.strip_prefix("vsw_sf_") {
+        return windows.get(win).map(|w| w.sf);
+    }
+    for (window, adcf) in adcfs.iter() {
+        if let Some(suffix) = varname.strip_prefix(window.as_str()) {
+            match suffix {
+                "_adcf" => return Some(adcf.adcf),
+                "_adcf_error" => return Some(adcf.err),
+                "_g" => return Some(adcf.g as f32),
+                "_p" => return Some(adcf.p as f32),
+                _ => ()
+            }
+        }
+    }
+    for (gas, aicf) in aicfs.iter() {
+        if varname == clargs.aicf_var_template.replace("{gas}", gas) {
+            return Some(aicf.aicf);
+        }else if varname == clargs.aicf_error_template.replace("{gas}", gas) {
+            return Some(aicf.err);
+        }
+    }
+    return None;
+}
+
+fn dump_failing_indices_driver(nc_file: &str, varname: &str, clargs: &CmdLineArgs) -> Result<bool, CheckError> {
+    // The same comparison as _all_equal_float, but listing each failing record instead of counting them
+    let (adcfs, aicfs) = load_correction_tables(clargs);
+    let (windows, _) = read_windows_table(clargs.release);
+    let table_value = match _expected_table_value(varname, &adcfs, &aicfs, &windows, clargs) {
+        Some(v) => v,
+        None => return Err(CheckError::Other(format!("'{}' is not an ADCF, AICF, or window scale factor variable, so has no expected value", varname)))
+    };
+
+    let nch = match netcdf::open(nc_file) {
+        Ok(h) => h,
+        Err(err) => return Err(CheckError::FileOpen(String::from(nc_file), err.to_string()))
+    };
+
+    // As in check_one_adcf, --adcf-from-attr expects the ADCFs to match the file's own attribute
+    let expected = if clargs.adcf_from_attr && varname.ends_with("_adcf") {
+        match _get_float_attribute_value(&nch, varname)? {
+            Some(v) => v,
+            None => return Err(CheckError::Other(format!("attribute '{}' is missing, so there is no expected ADCF", varname)))
+        }
+    }else{
+        table_value
+    };
+    let var = _get_var(&nch, varname)?;
+    let data = match clargs.first_n {
+        Some(n) => _get_first_records(&var, n, clargs)?,
+        None => _get_float_data(&var, clargs)?
+    };
+
+    let epsilon = _comparison_epsilon(varname, clargs);
+    let wrong_indices = _not_equal_indices(&data, expected, epsilon);
+    let n_wrong = wrong_indices.len();
+    if clargs.verbosity >= 0 {
+        for &i in wrong_indices.iter() {
+            println!("{}: {} (expected {})", i, data[i], expected);
+        }
+        println!("{}/{} values of {} differ from {} by more than {}", n_wrong, data.len(), varname, expected, epsilon);
+    }
+    return Ok(n_wrong == 0);
+}
+
+fn schema_driver(nc_file: &str, schema_file: &str, clargs: &CmdLineArgs) -> Result<bool, CheckError> {
+    // A schema replaces the built in GGG2020 checks entirely
+    let schema = read_schema_file(schema_file)?;
+    let nch = match netcdf::open(nc_file) {
+        Ok(h) => h,
+        Err(err) => return Err(CheckError::FileOpen(String::from(nc_file), err.to_string()))
+    };
+
+    let schema_ok = run_schema(&nch, &schema, clargs)?;
+    print_overall_result(nc_file, schema_ok, &[("schema", true, schema_ok)], clargs);
+
+    return Ok(schema_ok);
+}
+
+fn ncdump_driver(dump_file: &str, clargs: &CmdLineArgs) -> Result<bool, CheckError> {
+    // Without the netCDF file itself, only the checks that need just the variable names can run
+    let dump = match std::fs::read_to_string(dump_file) {
+        Ok(s) => s,
+        Err(err) => return Err(CheckError::FileRead(String::from(dump_file), err.to_string()))
+    };
+    let file_vars = parse_ncdump_variables(&dump)?;
+    let (windows, skipped_windows) = read_windows_table(clargs.release);
+
+    let windows_ok = check_included_windows(&file_vars, &windows, &skipped_windows, clargs)?;
+    let ingaas_ok = check_ingaas_variables(&file_vars, clargs)?;
+    let o2_window_ok = check_o2_airmass_window(&file_vars, clargs);
+    let cf_ok = if clargs.check_cf {check_channel_fringe(&file_vars, &windows, clargs)?} else {true};
+    let error_pairs_ok = if clargs.check_error_pairing {check_error_pairing(&file_vars, clargs)?} else {true};
+
+    let overall_ok = windows_ok && ingaas_ok && o2_window_ok && cf_ok && error_pairs_ok;
+    let categories = [
+        ("windows", true, windows_ok),
+        ("ingaas_vars", true, ingaas_ok),
+        ("o2_window", true, o2_window_ok),
+        ("cf", clargs.check_cf, cf_ok),
+        ("error_pairing", clargs.check_error_pairing, error_pairs_ok),
+    ];
+    print_overall_result(dump_file, overall_ok, &categories, clargs);
+
+    return Ok(overall_ok);
+}
+
+fn parse_ncdump_variables(dump: &str) -> Result<HashSet<String>, CheckError> {
+    // Variable declarations in `ncdump -h` output look like "\tfloat xco2(time) ;", with
+    // their attributes on the following lines as "\t\txco2:units = ... ;"
+    lazy_static! {
+        static ref RE: Regex = Regex::new(r"^\s*[a-z0-9]+ ([A-Za-z0-9_.\-]+)(\(.*\))? ;\s*$").unwrap();
+    }
+
+    let mut file_vars = HashSet::new();
+    let mut in_variables = false;
+    let mut found_section = false;
+    for line in dump.lines() {
+        let trimmed = line.trim();
+        if trimmed == "variables:" {
+            in_variables = true;
+            found_section = true;
+        }else if trimmed.starts_with("//") || trimmed == "data:" || trimmed == "}" {
+            in_variables = false;
+        }else if in_variables {
+            if let Some(caps) = RE.captures(line) {
+                file_vars.insert(caps[1].to_owned());
+            }
+        }
+    }
+
+    if !found_section {
+        return Err(CheckError::Other(String::from("No 'variables:' section found; is this the output of ncdump -h?")));
+    }
+    return Ok(file_vars);
+}
+
+const DEFAULT_PASS_TEMPLATE: &'static str = "{path} PASSES all tests - it appears to be a correct Phase 2 file";
+const DEFAULT_FAIL_TEMPLATE: &'static str = "{path} FAILS at least one test - it may be a Phase 1 file or there was a problem in processing.";
+
+fn print_overall_result(nc_file: &str, overall_ok: bool, categories: &[(&str, bool, bool)], clargs: &CmdLineArgs) {
+    // {categories} is the categories checked for a pass, and the ones that failed for a failure.
+    // --verdict-stderr sends this to stderr even with --quiet, which only silences stdout.
+    if clargs.verbosity < 0 && !clargs.verdict_stderr {
+        return;
+    }
+
+    let (template, cat_names): (&str, Vec<&str>) = if overall_ok {
+        (&clargs.pass_template, categories.iter().filter(|(_, ran, _)| *ran).map(|(c, _, _)| *c).collect())
+    }else{
+        (&clargs.fail_template, categories.iter().filter(|(_, ran, ok)| *ran && !*ok).map(|(c, _, _)| *c).collect())
+    };
+    let verdict = template.replace("{path}", nc_file).replace("{categories}", &cat_names.join(","));
+
+    if clargs.verdict_stderr {
+        eprintln!("{}", verdict);
+    }else{
+        if clargs.verbosity > 0 {println!();}
+        println!("{}", verdict);
+    }
+}
+
+// ************* //
+// MANIFEST MODE //
+// ************* //
+
+fn read_manifest(manifest_file: &str) -> Result<Vec<(String, u8)>, CheckError> {
+    // A CSV file with the columns path,expected_phase; a header line is optional
+    let contents = match std::fs::read_to_string(manifest_file) {
+        Ok(s) => s,
+        Err(err) => return Err(CheckError::FileRead(String::from(manifest_file), err.to_string()))
+    };
+
+    let mut entries = Vec::new();
+    for (i, line) in contents.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || (i == 0 && line.starts_with("path")) {
+            continue;
+        }
+
+        // Split on the last comma in case the path contains one
+        let (path, phase) = match line.rsplit_once(',') {
+            Some(parts) => parts,
+            None => return Err(CheckError::Other(format!("Could not parse manifest line {} ('{}'): expected path,expected_phase", i+1, line)))
+        };
+        let phase = match phase.trim() {
+            "1" => 1,
+            "2" => 2,
+            other => return Err(CheckError::Other(format!("Could not parse manifest line {} ('{}'): expected phase must be 1 or 2, got '{}'", i+1, line, other)))
+        };
+        entries.push((String::from(path.trim()), phase));
+    }
+
+    return Ok(entries);
+}
+
+fn _modified_within(nc_file: &str, window: std::time::Duration) -> bool {
+    // A file whose time can't be read is checked rather than skipped, so that it isn't missed
+    let modified = match std::fs::metadata(nc_file).and_then(|m| m.modified()) {
+        Ok(t) => t,
+        Err(_) => return true
+    };
+    match modified.elapsed() {
+        Ok(age) => age <= window,
+        Err(_) => true  // modified in the future
+    }
+}
+
+fn _parse_duration(value: &str) -> Option<std::time::Duration> {
+    // A number followed by s, m, h, or d, e.g. 24h
+    let value = value.trim();
+    let unit_seconds = match value.chars().last()? {
+        's' => 1,
+        'm' => 60,
+        'h' => 3600,
+        'd' => 86400,
+        _ => return None
+    };
+    let n = value[..value.len()-1].parse::<u64>().ok()?;
+    return Some(std::time::Duration::from_secs(n * unit_seconds));
+}
+
+fn read_resume_file(resume_file: &str) -> Result<HashSet<String>, CheckError> {
+    // One completed file path per line; a file that does not exist yet means nothing is done
+    match std::fs::read_to_string(resume_file) {
+        Ok(s) => Ok(s.lines().filter(|l| !l.is_empty()).map(String::from).collect()),
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(HashSet::new()),
+        Err(err) => Err(CheckError::FileRead(String::from(resume_file), err.to_string()))
+    }
+}
+
+fn append_resume_file(resume_file: &str, nc_file: &str) -> Result<(), CheckError> {
+    // Each path is added with a single append, so an interrupted run never leaves a partial line
+    use std::io::Write;
+    std::fs::OpenOptions::new().create(true).append(true).open(resume_file)
+        .and_then(|mut f| f.write_all(format!("{}\n", nc_file).as_bytes()))
+        .map_err(|e| CheckError::FileWrite(String::from(resume_file), format!("could not add {}: {}", nc_file, e)))
+}
+
+fn _canonical_path(path: &str, clargs: &CmdLineArgs) -> String {
+    // With --canonical-paths, reports name a file the same however it was given
+    if !clargs.canonical_paths {
+        return String::from(path);
+    }
+    match std::fs::canonicalize(path) {
+        Ok(p) => p.to_string_lossy().into_owned(),
+        Err(err) => {
+            eprintln!("WARNING: could not canonicalize {} ({}), so it is reported as given", path, err);
+            String::from(path)
+        }
+    }
+}
+
+fn _group_key(re: &Regex, nc_file: &str) -> String {
+    // The first capture group if there is one, otherwise the whole match
+    match re.captures(nc_file) {
+        Some(caps) => String::from(caps.get(1).or_else(|| caps.get(0)).unwrap().as_str()),
+        None => String::from("(no match)")
+    }
+}
+
+fn manifest_driver(manifest_file: &str, clargs: &CmdLineArgs) -> Result<bool, CheckError> {
+    // A file passing every check is taken to be Phase 2, and one failing any to be Phase 1
+    let entries: Vec<(String, u8)> = read_manifest(manifest_file)?.into_iter()
+        .map(|(path, phase)| (_canonical_path(&path, clargs), phase))
+        .collect();
+    let completed = match &clargs.resume_file {
+        Some(f) => read_resume_file(f)?,
+        None => HashSet::new()
+    };
+
+    let mut mismatches = Vec::new();
+    let mut skipped = Vec::new();
+    let mut n_resumed = 0;
+    // Each group is (number passed, number failed, number that could not be checked)
+    let mut groups: HashMap<String, (usize, usize, usize)> = HashMap::new();
+    let mut checksum_pairs = HashSet::new();
+    for (nc_file, expected_phase) in entries.iter() {
+        if completed.contains(nc_file) {
+            n_resumed += 1;
+            continue;
+        }
+        if let Some(window) = clargs.modified_within {
+            if !_modified_within(nc_file, window) {
+                skipped.push(nc_file);
+                continue;
+            }
+        }
+
+        let result = driver(nc_file, clargs);
+        if let Some(re) = &clargs.group_by {
+            let tally = groups.entry(_group_key(re, nc_file)).or_default();
+            match result {
+                Ok(true) => tally.0 += 1,
+                Ok(false) => tally.1 += 1,
+                Err(_) => tally.2 += 1
+            }
+        }
+
+        if clargs.check_checksums && result.is_ok() {
+            // Any problem reading the checksums was already reported by driver
+            if let Ok(nch) = netcdf::open(nc_file) {
+                if let Ok(pairs) = read_apriori_checksums(&nch, clargs) {
+                    checksum_pairs.extend(pairs);
+                }
+            }
+        }
+
+        let mismatch = match result {
+            Ok(passes) => {
+                // Failed files were still checked, so are recorded too; only errors are retried
+                if let Some(f) = &clargs.resume_file {
+                    append_resume_file(f, nc_file)?;
+                }
+                let found_phase = if passes {2} else {1};
+                if found_phase != *expected_phase {
+                    Some(format!("{} was expected to be Phase {} but looks like Phase {}", nc_file, expected_phase, found_phase))
+                }else{
+                    None
+                }
+            },
+            Err(msg) => Some(format!("{} could not be checked: {}", nc_file, msg))
+        };
+
+        if let Some(m) = mismatch {
+            mismatches.push(m);
+        }
+    }
+
+    if clargs.verbosity >= 0 {
+        let n_checked = entries.len() - skipped.len() - n_resumed;
+        println!("\n{}/{} files checked in {} match their expected phase", n_checked - mismatches.len(), n_checked, manifest_file);
+        if n_resumed > 0 {
+            println!("{} files were skipped because they were already checked by an earlier run", n_resumed);
+        }
+        if !skipped.is_empty() {
+            println!("{} files were skipped because they were not modified recently enough", skipped.len());
+        }
+        for m in mismatches.iter() {
+            println!("  MISMATCH: {}", m);
+        }
+        if clargs.verbosity >= 1 {
+            for f in skipped.iter() {
+                println!("  SKIPPED: {}", f);
+            }
+        }
+
+        let mut group_keys: Vec<&String> = groups.keys().collect();
+        group_keys.sort_unstable();
+        for key in group_keys {
+            let (n_pass, n_fail, n_error) = groups[key];
+            println!("  GROUP {}: {} passed, {} failed, {} could not be checked", key, n_pass, n_fail, n_error);
+        }
+    }
+
+    // Each file's own checksums were checked by driver, but not the ones across files
+    let checksums_ok = if clargs.check_checksums {
+        if clargs.verbosity > 1 {
+            _print_header("\n=== Checking a priori checksums across files ===", clargs);
+        }
+        check_apriori_checksum_mapping(&checksum_pairs, clargs)
+    } else {true};
+
+    return Ok(mismatches.is_empty() && checksums_ok);
+}
+
+
+// ********** //
+// BENCHMARKS //
+// ********** //
+
+fn run_benchmark(clargs: &CmdLineArgs) {
+    // Times the table parsing and the comparison kernels on synthetic data, so that there
+    // is a baseline to compare optimizations against without needing a real file
+    let repeats = clargs.benchmark_repeats.max(1);
+    let size = clargs.benchmark_size;
+
+    // Mostly matching values with a few wrong ones, so neither branch is optimized away
+    let data: Vec<f32> = (0..size).map(|i| if i % 1000 == 0 {1.5} else {1.0}).collect();
+
+    let mut results: Vec<(&'static str, usize, std::time::Duration)> = Vec::new();
+
+    let start = std::time::Instant::now();
+    let mut n_windows = 0;
+    for _ in 0..repeats {
+        n_windows += read_adcf_table(clargs.release).len() + read_aicf_table(clargs.release).len() + read_windows_table(clargs.release).0.len();
+    }
+    results.push(("table parsing (entries)", n_windows, start.elapsed()));
+
+    let start = std::time::Instant::now();
+    let mut n_wrong = 0;
+    for _ in 0..repeats {
+        n_wrong += _count_not_equal(&data, 1.0, DEFAULT_EPSILON);
+    }
+    results.push(("_all_equal_float", size * repeats, start.elapsed()));
+
+    let start = std::time::Instant::now();
+    for _ in 0..repeats {
+        n_wrong += _count_outside(&data, Some(0.0), Some(1.2));
+    }
+    results.push(("_count_out_of_range", size * repeats, start.elapsed()));
+
+    println!("Benchmark: {} repeats, {} elements per array ({} mismatches found)", repeats, size, n_wrong);
+    println!("{:<26} {:>14} {:>12} {:>16}", "kernel", "elements", "seconds", "elements/sec");
+    for (name, n, elapsed) in results {
+        let secs = elapsed.as_secs_f64();
+        let rate = if secs > 0.0 {n as f64 / secs} else {f64::INFINITY};
+        println!("{:<26} {:>14} {:>12.4} {:>16.3e}", name, n, secs, rate);
+    }
+}
+
+
+// **************** //
+// RESULTS DATABASE //
+// **************** //
+
+fn record_validation(db_file: &str, nc_file: &str, overall_ok: bool, categories: &[(&str, bool, bool)]) -> Result<(), CheckError> {
+    // One row per checked file in `validations`, plus one row per category that was run in
+    // `validation_categories`, so that e.g. the history of a single category can be queried
+    let mut conn = match rusqlite::Connection::open(db_file) {
+        Ok(c) => c,
+        Err(err) => return Err(CheckError::Database(db_file.to_owned(), format!("unable to open: {}", err)))
+    };
+
+    let tx = match conn.transaction() {
+        Ok(t) => t,
+        Err(err) => return Err(CheckError::Database(db_file.to_owned(), format!("unable to start a transaction: {}", err)))
+    };
+    let result = tx.execute_batch(
+        "CREATE TABLE IF NOT EXISTS validations (
+            id INTEGER PRIMARY KEY,
+            file_path TEXT NOT NULL,
+            checked_at TEXT NOT NULL,
+            overall_pass INTEGER NOT NULL,
+            n_categories INTEGER NOT NULL,
+            n_failed INTEGER NOT NULL,
+            failed_categories TEXT NOT NULL
+        );
+        CREATE TABLE IF NOT EXISTS validation_categories (
+            validation_id INTEGER NOT NULL REFERENCES validations(id),
+            category TEXT NOT NULL,
+            pass INTEGER NOT NULL
+        );
+        CREATE TABLE IF NOT EXISTS report_meta (
+            key TEXT PRIMARY KEY,
+            value TEXT NOT NULL
+        );"
+    );
+    if let Err(err) = result {
+        return Err(CheckError::Database(db_file.to_owned(), format!("unable to create the tables: {}", err)));
+    }
+
+    // Stamp a new database with the schema version, and refuse to add rows to one written with a different schema
+    let result = tx.execute(
+        "INSERT OR IGNORE INTO report_meta (key, value) VALUES ('schema_version', ?1)",
+        rusqlite::params![REPORT_SCHEMA_VERSION.to_string()]
+    );
+    if let Err(err) = result {
+        return Err(CheckError::Database(db_file.to_owned(), format!("unable to record the schema version: {}", err)));
+    }
+    let db_version: String = match tx.query_row("SELECT value FROM report_meta WHERE key = 'schema_version'", [], |row| row.get(0)) {
+        Ok(v) => v,
+        Err(err) => return Err(CheckError::Database(db_file.to_owned(), format!("unable to read the schema version: {}", err)))
+    };
+    if db_version != REPORT_SCHEMA_VERSION.to_string() {
+        return Err(CheckError::Database(db_file.to_owned(), format!("it has schema version {}, but this program writes version {}", db_version, REPORT_SCHEMA_VERSION)));
+    }
+
+    let run_categories: Vec<&(&str, bool, bool)> = categories.iter().filter(|(_, ran, _)| *ran).collect();
+    let failed: Vec<&str> = run_categories.iter().filter(|(_, _, ok)| !*ok).map(|(name, _, _)| *name).collect();
+    let file_path = std::fs::canonicalize(nc_file)
+        .map(|p| p.to_string_lossy().into_owned())
+        .unwrap_or_else(|_| String::from(nc_file));
+    let checked_at = chrono::Utc::now().to_rfc3339();
+
+    let result = tx.execute(
+        "INSERT INTO validations (file_path, checked_at, overall_pass, n_categories, n_failed, failed_categories) VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+        rusqlite::params![file_path, checked_at, overall_ok, run_categories.len() as i64, failed.len() as i64, failed.join(",")]
+    );
+    if let Err(err) = result {
+        return Err(CheckError::Database(db_file.to_owned(), format!("unable to add the results for {}: {}", nc_file, err)));
+    }
+
+    let validation_id = tx.last_insert_rowid();
+    for (name, _, ok) in run_categories {
+        let result = tx.execute(
+            "INSERT INTO validation_categories (validation_id, category, pass) VALUES (?1, ?2, ?3)",
+            rusqlite::params![validation_id, name, ok]
+        );
+        if let Err(err) = result {
+            return Err(CheckError::Database(db_file.to_owned(), format!("unable to add the {} result for {}: {}", name, nc_file, err)));
+        }
+    }
+
+    match tx.commit() {
+        Ok(_) => Ok(()),
+        Err(err) => Err(CheckError::Database(db_file.to_owned(), format!("unable to save the results for {}: {}", nc_file, err)))
+    }
+}
+
+
+// ****************** //
+// PROMETHEUS METRICS //
+// ****************** //
+
+fn _prometheus_label_value(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n")
+}
+
+fn record_prometheus_metrics(prom_file: &str, nc_file: &str, overall_ok: bool, categories: &[(&str, bool, bool)]) -> Result<(), CheckError> {
+    // The textfile collector reads the whole file each scrape, so this replaces any series for
+    // this file and keeps those for files checked earlier (e.g. by --manifest or --watch)
+    let file_path = std::fs::canonicalize(nc_file)
+        .map(|p| p.to_string_lossy().into_owned())
+        .unwrap_or_else(|_| String::from(nc_file));
+    let file_label = format!("{{file=\"{}\"", _prometheus_label_value(&file_path));
+
+    let mut series: Vec<String> = match std::fs::read_to_string(prom_file) {
+        Ok(text) => text.lines()
+            .filter(|line| !line.starts_with('#') && !line.trim().is_empty())
+            .filter(|line| !line.starts_with("ggg_phase2_report_schema_version"))
+            .filter(|line| !line.contains(&format!("{}}}", file_label)) && !line.contains(&format!("{},", file_label)))
+            .map(String::from)
+            .collect(),
+        Err(_) => Vec::new()
+    };
+
+    series.push(format!("ggg_phase2_pass{}}} {}", file_label, overall_ok as u8));
+    for (name, _, ok) in categories.iter().filter(|(_, ran, _)| *ran) {
+        series.push(format!("ggg_phase2_category_fail{},category=\"{}\"}} {}", file_label, name, !*ok as u8));
+    }
+    series.sort();
+
+    let mut text = format!("# HELP ggg_phase2_report_schema_version Version of the metrics written to this file\n\
+                            # TYPE ggg_phase2_report_schema_version gauge\n\
+                            ggg_phase2_report_schema_version {}\n", REPORT_SCHEMA_VERSION);
+    for (metric, help) in [("ggg_phase2_category_fail", "1 if this category of checks failed for the file, 0 if it passed"),
+                           ("ggg_phase2_pass", "1 if the file passed all the checks that were run, 0 otherwise")].iter() {
+        text.push_str(&format!("# HELP {} {}\n# TYPE {} gauge\n", metric, help, metric));
+        for line in series.iter().filter(|l| l.starts_with(&format!("{}{{", metric))) {
+            text.push_str(line);
+            text.push('\n');
+        }
+    }
+
+    // Written to a temporary file then renamed, so the collector never reads a partial file
+    let tmp_file = format!("{}.tmp", prom_file);
+    std::fs::write(&tmp_file, text)
+        .and_then(|_| std::fs::rename(&tmp_file, prom_file))
+        .map_err(|e| CheckError::FileWrite(String::from(prom_file), e.to_string()))
+}
+
+
+// ********** //
+// WATCH MODE //
+// ********** //
+
+// How long a new file must go without further events before we check it, and how many
+// times to retry if it still can't be opened (i.e. is probably still being written)
+const WATCH_SETTLE_TIME: std::time::Duration = std::time::Duration::from_secs(2);
+const WATCH_MAX_OPEN_ATTEMPTS: u32 = 5;
+
+fn watch_directory(watch_dir: &str, clargs: &CmdLineArgs) -> Result<(), CheckError> {
+    use notify::{EventKind, RecursiveMode, Watcher};
+    use std::sync::mpsc::RecvTimeoutError;
+
+    let (tx, rx) = std::sync::mpsc::channel();
+    let mut watcher = match notify::recommended_watcher(tx) {
+        Ok(w) => w,
+        Err(err) => return Err(CheckError::Other(format!("Unable to create a watcher for {}: {}", watch_dir, err)))
+    };
+    if let Err(err) = watcher.watch(std::path::Path::new(watch_dir), RecursiveMode::NonRecursive) {
+        return Err(CheckError::Other(format!("Unable to watch {}: {}", watch_dir, err)));
+    }
+
+    if clargs.verbosity >= 0 {
+        println!("Watching {} for new .nc files (press Ctrl-C to stop)", watch_dir);
+    }
+
+    // Files seen but not checked yet, with the time of their last event and failed opens
+    let mut pending: HashMap<std::path::PathBuf, (std::time::Instant, u32)> = HashMap::new();
+    loop {
+        match rx.recv_timeout(std::time::Duration::from_millis(500)) {
+            Ok(Ok(event)) => {
+                // Renames count as new files since many writers create a temporary file
+                // and move it into place when done
+                let is_new = matches!(event.kind, EventKind::Create(_) | EventKind::Modify(notify::event::ModifyKind::Name(_)));
+                let is_write = matches!(event.kind, EventKind::Modify(_));
+                for path in event.paths {
+                    if path.extension() != Some(std::ffi::OsStr::new("nc")) {
+                        continue;
+                    }
+
+                    if is_new {
+                        pending.insert(path, (std::time::Instant::now(), 0));
+                    }else if is_write {
+                        if let Some(entry) = pending.get_mut(&path) {
+                            entry.0 = std::time::Instant::now();
+                        }
+                    }
+                }
+            },
+            Ok(Err(err)) => {
+                if clargs.verbosity >= 0 {
+                    eprintln!("WARNING: error while watching {}: {}", watch_dir, err);
+                }
+            },
+            Err(RecvTimeoutError::Timeout) => {},
+            Err(RecvTimeoutError::Disconnected) => return Err(CheckError::Other(format!("Stopped receiving events for {}", watch_dir)))
+        }
+
+        let mut settled: Vec<std::path::PathBuf> = pending.iter()
+            .filter(|(_, (last_event, _))| last_event.elapsed() >= WATCH_SETTLE_TIME)
+            .map(|(path, _)| path.clone())
+            .collect();
+        settled.sort_unstable();
+
+        for path in settled {
+            let attempts = pending[&path].1 + 1;
+            if netcdf::open(&path).is_err() && attempts < WATCH_MAX_OPEN_ATTEMPTS {
+                pending.insert(path, (std::time::Instant::now(), attempts));
+                continue;
+            }
+
+            pending.remove(&path);
+            let path_str = path.to_string_lossy();
+            if let Err(msg) = driver(&path_str, clargs) {
+                eprintln!("ERROR: {}: {}", path_str, msg);
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct CmdLineArgs {
+    nc_file: Option<String>,
+    verbosity: i8,
+    failures_only: bool,
+    exclude_vars: Vec<String>,
+    allowed_missing_windows: Vec<String>,
+    pass_template: String,
+    list_categories: bool,
+    verdict_stderr: bool,
+    dump_failing_indices: Option<String>,
+    fail_template: String,
+    tolerances: Vec<(String, f32)>,
+    rules_file: Option<String>,
+    reference_url: Option<String>,
+    check_cf: bool,
+    check_zo: bool,
+    zo_max: f32,
+    check_error_pairing: bool,
+    check_priors: bool,
+    density_closure_tolerance: f32,
+    check_lse: bool,
+    lse_threshold: f32,
+    check_ak: bool,
+    adcf_from_attr: bool,
+    check_gravity: bool,
+    gravity_tolerance: f32,
+    check_h2o_dmf: bool,
+    h2o_dmf_threshold: f32,
+    check_met: bool,
+    calm_wdir: String,
+    check_pointing: bool,
+    dip_max: f32,
+    mvd_max: f32,
+    check_fs: bool,
+    fs_threshold: f32,
+    check_rms: bool,
+    rms_threshold: f32,
+    check_nit: bool,
+    nit_max: usize,
+    check_continuum: bool,
+    ct_max: f32,
+    cc_max: f32,
+    check_airmass: bool,
+    check_ovc: bool,
+    ovc_factor: Option<f32>,
+    check_vsf_error: bool,
+    vsf_error_fraction: f32,
+    check_vsf_consistency: bool,
+    check_checksums: bool,
+    check_time: bool,
+    check_sg: bool,
+    hash_file: Option<String>,
+    check_error_prop: bool,
+    release: &'static Release,
+    check_hf: bool,
+    hf_limits: Vec<(f32, f32)>,
+    hf_fail: bool,
+    error_prop_tolerance: f32,
+    sg_expected: f32,
+    sg_tolerance: f32,
+    earliest_time: NaiveDateTime,
+    vsf_consistency_factor: f32,
+    check_zpres: bool,
+    zpres_tolerance: f32,
+    zpres_pout_tolerance: f32,
+    check_instrument: bool,
+    graw_opd_product: Option<f32>,
+    graw_opd_tolerance: f32,
+    check_sia: bool,
+    fvsi_max: f32,
+    expected_fvsi_max: Option<f32>,
+    check_geo: bool,
+    azim_convention: String,
+    check_o2: bool,
+    o2_tolerance: f32,
+    check_cells: bool,
+    schema_file: Option<String>,
+    compact_fail: bool,
+    sqlite_file: Option<String>,
+    prometheus_file: Option<String>,
+    emit_tables: Option<String>,
+    diff_tables: Option<(String, String)>,
+    benchmark: bool,
+    benchmark_size: usize,
+    benchmark_repeats: usize,
+    aicf_var_template: String,
+    aicf_error_template: String,
+    attrs_only: bool,
+    encoding_report: bool,
+    fail_on_missing_attr: bool,
+    watch_dir: Option<String>,
+    from_ncdump: Option<String>,
+    manifest: Option<String>,
+    modified_within: Option<std::time::Duration>,
+    group_by: Option<Regex>,
+    canonical_paths: bool,
+    expected_vars_alt: Option<String>,
+    match_mode: String,
+    resume_file: Option<String>,
+    since: Option<NaiveDateTime>,
+    first_n: Option<usize>,
+    max_memory_mb: Option<usize>,
+    // Not a command line option: set per file by the driver when --since is given
+    record_mask: Option<Vec<bool>>
+}
+
+fn parse_clargs() -> CmdLineArgs {
+    return parse_clargs_from(std::env::args_os());
+}
+
+fn parse_clargs_from<I, T>(args: I) -> CmdLineArgs
+where I: IntoIterator<Item = T>, T: Into<std::ffi::OsString> + Clone {
+    // Separate from parse_clargs so that the tests can give their own command line
+    let yml = clap::load_yaml!("clargs.yml");
+    let clargs = clap::App::from_yaml(yml).version(clap::crate_version!()).get_matches_from(args);
+
+    let nc_file = clargs.value_of("nc_file").map(String::from);
+    let nverb = clargs.occurrences_of("verbose");
+    let nquiet = clargs.occurrences_of("quiet");
+    let failures_only = clargs.occurrences_of("failures_only") > 0;
+    let exclude_vars = clargs.values_of("exclude_var").map(|v| v.map(String::from).collect()).unwrap_or_default();
+    let allowed_missing_windows = clargs.values_of("allow_missing_window").map(|v| v.map(String::from).collect()).unwrap_or_default();
+    let pass_template = String::from(clargs.value_of("pass_template").unwrap_or(DEFAULT_PASS_TEMPLATE));
+    let fail_template = String::from(clargs.value_of("fail_template").unwrap_or(DEFAULT_FAIL_TEMPLATE));
+    let verdict_stderr = clargs.occurrences_of("verdict_stderr") > 0;
+    let list_categories = clargs.occurrences_of("list_categories") > 0;
+    let dump_failing_indices = clargs.value_of("dump_failing_indices").map(String::from);
+    let tolerances = clargs.values_of("tolerance").map(|v| v.map(_parse_tolerance_arg).collect()).unwrap_or_default();
+    let rules_file = clargs.value_of("rules").map(String::from);
+    let reference_url = clargs.value_of("reference_url").map(String::from);
+    let check_cf = clargs.occurrences_of("check_cf") > 0;
+    let check_zo = clargs.occurrences_of("check_zo") > 0;
+    let zo_max = _parse_float_arg(&clargs, "zo_max", 0.1);
+    let check_error_pairing = clargs.occurrences_of("check_error_pairing") > 0;
+    let check_priors = clargs.occurrences_of("check_priors") > 0;
+    let density_closure_tolerance = _parse_float_arg(&clargs, "density_closure_tolerance", 0.01);
+    let check_lse = clargs.occurrences_of("check_lse") > 0;
+    let lse_threshold = _parse_float_arg(&clargs, "lse_threshold", 0.01);
+    let check_ak = clargs.occurrences_of("check_ak") > 0;
+    let adcf_from_attr = clargs.occurrences_of("adcf_from_attr") > 0;
+    let check_gravity = clargs.occurrences_of("check_gravity") > 0;
+    let gravity_tolerance = _parse_float_arg(&clargs, "gravity_tolerance", 0.001);
+    let check_h2o_dmf = clargs.occurrences_of("check_h2o_dmf") > 0;
+    let h2o_dmf_threshold = _parse_float_arg(&clargs, "h2o_dmf_threshold", 0.25);
+    let check_met = clargs.occurrences_of("check_met") > 0;
+    let calm_wdir = String::from(clargs.value_of("calm_wdir").unwrap_or("zero"));
+    let check_pointing = clargs.occurrences_of("check_pointing") > 0;
+    let dip_max = _parse_float_arg(&clargs, "dip_max", 0.1);
+    let mvd_max = _parse_float_arg(&clargs, "mvd_max", 8.0);
+    let check_fs = clargs.occurrences_of("check_fs") > 0;
+    let fs_threshold = _parse_float_arg(&clargs, "fs_threshold", 10.0);
+    let check_rms = clargs.occurrences_of("check_rms") > 0;
+    let rms_threshold = _parse_float_arg(&clargs, "rms_threshold", 5.0);
+    let check_nit = clargs.occurrences_of("check_nit") > 0;
+    let nit_max = _parse_count_arg(&clargs, "nit_max", 15);
+    let check_continuum = clargs.occurrences_of("check_continuum") > 0;
+    let ct_max = _parse_float_arg(&clargs, "ct_max", 1.0);
+    let cc_max = _parse_float_arg(&clargs, "cc_max", 1.0);
+    let check_airmass = clargs.occurrences_of("check_airmass") > 0;
+    let check_ovc = clargs.occurrences_of("check_ovc") > 0;
+    let ovc_factor = if clargs.is_present("ovc_factor") {Some(_parse_float_arg(&clargs, "ovc_factor", 1.0))} else {None};
+    let check_vsf_error = clargs.occurrences_of("check_vsf_error") > 0;
+    let vsf_error_fraction = _parse_float_arg(&clargs, "vsf_error_fraction", 1.0);
+    let check_vsf_consistency = clargs.occurrences_of("check_vsf_consistency") > 0;
+    let check_checksums = clargs.occurrences_of("check_checksums") > 0;
+    let check_time = clargs.occurrences_of("check_time") > 0;
+    let check_sg = clargs.occurrences_of("check_sg") > 0;
+    let hash_file = clargs.value_of("hash_file").map(String::from);
+    let check_error_prop = clargs.occurrences_of("check_error_prop") > 0;
+    let error_prop_tolerance = _parse_float_arg(&clargs, "error_prop_tolerance", 0.01);
+    let release = _parse_release_arg(&clargs);
+    let check_hf = clargs.occurrences_of("check_hf") > 0;
+    let hf_limits = _parse_hf_limits_arg(&clargs);
+    let hf_fail = clargs.occurrences_of("hf_fail") > 0;
+    let sg_expected = _parse_float_arg(&clargs, "sg_expected", 0.0);
+    let sg_tolerance = _parse_float_arg(&clargs, "sg_tolerance", 10.0);
+    let earliest_time = match clargs.value_of("earliest_time") {
+        Some(v) => _parse_datetime(v).unwrap_or_else(|| {
+            let msg = format!("The value '{}' for --earliest-time is not an ISO 8601 date or date and time", v);
+            clap::Error::value_validation_auto(msg).exit()
+        }),
+        None => NaiveDate::from_ymd_opt(2004, 1, 1).unwrap().and_hms_opt(0, 0, 0).unwrap()
+    };
+    let vsf_consistency_factor = _parse_float_arg(&clargs, "vsf_consistency_factor", 3.0);
+    let check_zpres = clargs.occurrences_of("check_zpres") > 0;
+    let zpres_tolerance = _parse_float_arg(&clargs, "zpres_tolerance", 0.001);
+    let zpres_pout_tolerance = _parse_float_arg(&clargs, "zpres_pout_tolerance", 10.0);
+    let check_instrument = clargs.occurrences_of("check_instrument") > 0;
+    let graw_opd_product = if clargs.is_present("graw_opd_product") {Some(_parse_float_arg(&clargs, "graw_opd_product", 0.5))} else {None};
+    let graw_opd_tolerance = _parse_float_arg(&clargs, "graw_opd_tolerance", 0.01);
+    let check_sia = clargs.occurrences_of("check_sia") > 0;
+    let fvsi_max = _parse_float_arg(&clargs, "fvsi_max", 0.1);
+    let expected_fvsi_max = if clargs.is_present("expected_fvsi_max") {Some(_parse_float_arg(&clargs, "expected_fvsi_max", 0.0))} else {None};
+    let check_geo = clargs.occurrences_of("check_geo") > 0;
+    let azim_convention = String::from(clargs.value_of("azim_convention").unwrap_or("positive"));
+    let check_o2 = clargs.occurrences_of("check_o2") > 0;
+    let o2_tolerance = _parse_float_arg(&clargs, "o2_tolerance", 0.02);
+    let check_cells = clargs.occurrences_of("check_cells") > 0;
+    let schema_file = clargs.value_of("schema").map(String::from);
+    let compact_fail = clargs.occurrences_of("compact_fail") > 0 && nquiet == 0;
+    let sqlite_file = clargs.value_of("sqlite").map(String::from);
+    let prometheus_file = clargs.value_of("prometheus").map(String::from);
+    let emit_tables = clargs.value_of("emit_tables").map(String::from);
+    let diff_tables = clargs.values_of("diff_tables").map(|mut v| (String::from(v.next().unwrap()), String::from(v.next().unwrap())));
+    if let Some((table_kind, _)) = &diff_tables {
+        if !["adcf", "aicf", "windows"].contains(&table_kind.as_str()) {
+            let msg = format!("The table '{}' for --diff-tables must be one of adcf, aicf, or windows", table_kind);
+            clap::Error::value_validation_auto(msg).exit()
+        }
+    }
+    let benchmark = clargs.occurrences_of("benchmark") > 0;
+    let benchmark_size = _parse_count_arg(&clargs, "benchmark_size", 1_000_000);
+    let benchmark_repeats = _parse_count_arg(&clargs, "benchmark_repeats", 20);
+    let aicf_var_template = _parse_gas_template_arg(&clargs, "aicf_var_template", "{gas}_aicf");
+    let aicf_error_template = _parse_gas_template_arg(&clargs, "aicf_error_template", "{gas}_aicf_error");
+    let attrs_only = clargs.occurrences_of("attrs_only") > 0;
+    let encoding_report = clargs.occurrences_of("encoding_report") > 0;
+    let fail_on_missing_attr = clargs.occurrences_of("fail_on_missing_attr") > 0;
+    let watch_dir = clargs.value_of("watch").map(String::from);
+    let from_ncdump = clargs.value_of("from_ncdump").map(String::from);
+    let manifest = clargs.value_of("manifest").map(String::from);
+    let since = clargs.value_of("since").map(|v| {
+        _parse_datetime(v).unwrap_or_else(|| {
+            let msg = format!("The value '{}' for --since is not an ISO 8601 date or date and time", v);
+            clap::Error::value_validation_auto(msg).exit()
+        })
+    });
+    let modified_within = clargs.value_of("modified_within").map(|v| {
+        _parse_duration(v).unwrap_or_else(|| {
+            let msg = format!("The value '{}' for --modified-within is not a duration such as 90m, 24h, or 7d", v);
+            clap::Error::value_validation_auto(msg).exit()
+        })
+    });
+    let group_by = clargs.value_of("group_by").map(|v| {
+        Regex::new(v).unwrap_or_else(|err| {
+            let msg = format!("The value '{}' for --group-by is not a valid regular expression: {}", v, err);
+            clap::Error::value_validation_auto(msg).exit()
+        })
+    });
+    let resume_file = clargs.value_of("resume").map(String::from);
+    let canonical_paths = clargs.occurrences_of("canonical_paths") > 0;
+    let expected_vars_alt = clargs.value_of("expected_vars_alt").map(String::from);
+    let match_mode = String::from(clargs.value_of("match_mode").unwrap_or("any"));
+    let first_n = if clargs.is_present("first_n") {Some(_parse_count_arg(&clargs, "first_n", 0))} else {None};
+    // Zero records would leave every value check with nothing to check, so it would always pass
+    if first_n == Some(0) {
+        clap::Error::value_validation_auto(String::from("The value '0' for --first-n must be at least 1")).exit()
+    }
+    let max_memory_mb = if clargs.is_present("max_memory") {Some(_parse_count_arg(&clargs, "max_memory", 0))} else {None};
+
+    let args = CmdLineArgs{
+        nc_file: nc_file,
+        verbosity: if nquiet > 0 {-1} else {nverb as i8},
+        failures_only: failures_only,
+        exclude_vars: exclude_vars,
+        allowed_missing_windows: allowed_missing_windows,
+        pass_template: pass_template,
+        list_categories: list_categories,
+        verdict_stderr: verdict_stderr,
+        dump_failing_indices: dump_failing_indices,
+        fail_template: fail_template,
+        tolerances: tolerances,
+        rules_file: rules_file,
+        reference_url: reference_url,
+        check_cf: check_cf,
+        check_zo: check_zo,
+        zo_max: zo_max,
+        check_error_pairing: check_error_pairing,
+        check_priors: check_priors,
+        density_closure_tolerance: density_closure_tolerance,
+        check_lse: check_lse,
+        lse_threshold: lse_threshold,
+        check_ak: check_ak,
+        adcf_from_attr: adcf_from_attr,
+        check_gravity: check_gravity,
+        gravity_tolerance: gravity_tolerance,
+        check_h2o_dmf: check_h2o_dmf,
+        h2o_dmf_threshold: h2o_dmf_threshold,
+        check_met: check_met,
+        calm_wdir: calm_wdir,
+        check_pointing: check_pointing,
+        dip_max: dip_max,
+        mvd_max: mvd_max,
+        check_fs: check_fs,
+        fs_threshold: fs_threshold,
+        check_rms: check_rms,
+        rms_threshold: rms_threshold,
+        check_nit: check_nit,
+        nit_max: nit_max,
+        check_continuum: check_continuum,
+        ct_max: ct_max,
+        cc_max: cc_max,
+        check_airmass: check_airmass,
+        check_ovc: check_ovc,
+        ovc_factor: ovc_factor,
+        check_vsf_error: check_vsf_error,
+        vsf_error_fraction: vsf_error_fraction,
+        check_vsf_consistency: check_vsf_consistency,
+        check_checksums: check_checksums,
+        check_time: check_time,
+        check_sg: check_sg,
+        hash_file: hash_file,
+        check_error_prop: check_error_prop,
+        error_prop_tolerance: error_prop_tolerance,
+        release: release,
+        check_hf: check_hf,
+        hf_limits: hf_limits,
+        hf_fail: hf_fail,
+        sg_expected: sg_expected,
+        sg_tolerance: sg_tolerance,
+        earliest_time: earliest_time,
+        vsf_consistency_factor: vsf_consistency_factor,
+        check_zpres: check_zpres,
+        zpres_tolerance: zpres_tolerance,
+        zpres_pout_tolerance: zpres_pout_tolerance,
+        check_instrument: check_instrument,
+        graw_opd_product: graw_opd_product,
+        graw_opd_tolerance: graw_opd_tolerance,
+        check_sia: check_sia,
+        fvsi_max: fvsi_max,
+        expected_fvsi_max: expected_fvsi_max,
+        check_geo: check_geo,
+        azim_convention: azim_convention,
+        check_o2: check_o2,
+        o2_tolerance: o2_tolerance,
+        check_cells: check_cells,
+        schema_file: schema_file,
+        compact_fail: compact_fail,
+        sqlite_file: sqlite_file,
+        prometheus_file: prometheus_file,
+        emit_tables: emit_tables,
+        diff_tables: diff_tables,
+        benchmark: benchmark,
+        benchmark_size: benchmark_size,
+        benchmark_repeats: benchmark_repeats,
+        aicf_var_template: aicf_var_template,
+        aicf_error_template: aicf_error_template,
+        attrs_only: attrs_only,
+        encoding_report: encoding_report,
+        fail_on_missing_attr: fail_on_missing_attr,
+        watch_dir: watch_dir,
+        from_ncdump: from_ncdump,
+        manifest: manifest,
+        modified_within: modified_within,
+        group_by: group_by,
+        canonical_paths: canonical_paths,
+        expected_vars_alt: expected_vars_alt,
+        match_mode: match_mode,
+        resume_file: resume_file,
+        since: since,
+        first_n: first_n,
+        max_memory_mb: max_memory_mb,
+        record_mask: None
+    };
+
+    return args;
+}
+
+fn _parse_gas_template_arg(clargs: &clap::ArgMatches, arg_name: &str, default: &str) -> String {
+    let value = clargs.value_of(arg_name).unwrap_or(default);
+    if !value.contains("{gas}") {
+        let msg = format!("The value '{}' for --{} must contain {{gas}}", value, arg_name.replace('_', "-"));
+        clap::Error::value_validation_auto(msg).exit()
+    }
+    return String::from(value);
+}
+
+fn _parse_release_arg(clargs: &clap::ArgMatches) -> &'static Release {
+    let name = match clargs.value_of("release") {
+        Some(n) => n,
+        None => return &RELEASES[0]
+    };
+    match RELEASES.iter().find(|r| r.name == name) {
+        Some(r) => r,
+        None => {
+            let names: Vec<&str> = RELEASES.iter().map(|r| r.name).collect();
+            let msg = format!("The value '{}' for --release is not a known release (known releases: {})", name, names.join(", "));
+            clap::Error::value_validation_auto(msg).exit()
+        }
+    }
+}
+
+fn _parse_hf_limits_arg(clargs: &clap::ArgMatches) -> Vec<(f32, f32)> {
+    let value = clargs.value_of("hf_limits").unwrap_or("30:150,60:250,90:350");
+    let mut limits = Vec::new();
+    for band in value.split(',') {
+        let parsed = band.split_once(':').and_then(|(lat, max)| {
+            match (lat.trim().parse::<f32>(), max.trim().parse::<f32>()) {
+                (Ok(l), Ok(m)) if l.is_finite() && m.is_finite() => Some((l, m)),
+                _ => None
+            }
+        });
+        match parsed {
+            Some(limit) => limits.push(limit),
+            None => {
+                let msg = format!("The band '{}' for --hf-limits must be LAT:MAX with finite numbers, e.g. 30:150", band);
+                clap::Error::value_validation_auto(msg).exit()
+            }
+        }
+    }
+    limits.sort_by(|a, b| a.0.total_cmp(&b.0));
+    return limits;
+}
+
+fn _parse_count_arg(clargs: &clap::ArgMatches, arg_name: &str, default: usize) -> usize {
+    let value = match clargs.value_of(arg_name) {
+        Some(v) => v,
+        None => return default
+    };
+    match value.parse::<usize>() {
+        Ok(v) => v,
+        Err(_) => {
+            let msg = format!("The value '{}' for --{} is not a non-negative integer", value, arg_name.replace('_', "-"));
+            clap::Error::value_validation_auto(msg).exit()
+        }
+    }
+}
+
+fn _parse_tolerance_arg(value: &str) -> (String, f32) {
+    let parsed = value.split_once('=').and_then(|(key, eps)| {
+        let eps = eps.trim().parse::<f32>().ok().filter(|e| *e >= 0.0)?;
+        Some((key.trim().to_owned(), eps))
+    });
+    match parsed {
+        Some(p) if !p.0.is_empty() => p,
+        _ => {
+            let msg = format!("The value '{}' for --tolerance is not of the form <gas or variable>=<epsilon>", value);
+            clap::Error::value_validation_auto(msg).exit()
+        }
+    }
+}
+
+fn _parse_float_arg(clargs: &clap::ArgMatches, arg_name: &str, default: f32) -> f32 {
+    // Defaults are applied here rather than with clap's default_value, because any
+    // argument with a default counts as given and stops ArgRequiredElseHelp working
+    let value = match clargs.value_of(arg_name) {
+        Some(v) => v,
+        None => return default
+    };
+    match value.parse::<f32>() {
+        Ok(v) => v,
+        Err(_) => {
+            let msg = format!("The value '{}' for --{} is not a number", value, arg_name.replace('_', "-"));
+            clap::Error::value_validation_auto(msg).exit()
+        }
+    }
+}
+
+fn main() {
+    let clargs = parse_clargs();
+
+    if clargs.benchmark {
+        run_benchmark(&clargs);
+        std::process::exit(0);
+    }
+
+    if clargs.list_categories {
+        list_categories();
+        std::process::exit(0);
+    }
+
+    if let Some(out_dir) = &clargs.emit_tables {
+        if let Err(msg) = emit_tables(out_dir, &clargs) {
+            eprintln!("ERROR: {}", msg);
+            std::process::exit(2);
+        }
+        std::process::exit(0);
+    }
+
+    if let Some((table_kind, table_file)) = &clargs.diff_tables {
+        match diff_tables(table_kind, table_file, &clargs) {
+            Ok(same) => std::process::exit(if same {0} else {1}),
+            Err(msg) => {
+                eprintln!("ERROR: {}", msg);
+                std::process::exit(2);
+            }
+        }
+    }
+
+    if let Some(watch_dir) = &clargs.watch_dir {
+        // Only returns if the watch could not be set up or stopped unexpectedly
+        if let Err(msg) = watch_directory(watch_dir, &clargs) {
+            eprintln!("ERROR: {}", msg);
+        }
+        std::process::exit(2);
+    }
+
+    let result = if let Some(dump_file) = &clargs.from_ncdump {
+        ncdump_driver(&_canonical_path(dump_file, &clargs), &clargs)
+    }else if let Some(manifest_file) = &clargs.manifest {
+        manifest_driver(manifest_file, &clargs)
+    }else{
+        // clap requires the file whenever we are not in watch, ncdump, or manifest mode
+        driver(&_canonical_path(clargs.nc_file.as_ref().unwrap(), &clargs), &clargs)
+    };
+
+    match result {
+        Ok(passes) => {
+            if passes {std::process::exit(0);}
+            else {std::process::exit(1);}
+        },
+        Err(msg) => {
+            eprintln!("ERROR: {}", msg);
+            std::process::exit(2);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn adcf_row_with_comma_decimals_is_rejected() {
+        let table = " Gas         ADCF      ADCF_Err  g    p\n\"xco2_6220\"  -0,00903  0,00025   15   4";
+        match parse_adcf_table(table) {
+            Err(CheckError::TableParse(_, reason)) => assert!(reason.contains("'.' as the decimal separator"), "unexpected reason: {}", reason),
+            other => panic!("expected a TableParse error, got {:?}", other)
+        }
+    }
+
+    #[test]
+    fn adcf_row_with_dot_decimals_parses() {
+        let table = " Gas         ADCF      ADCF_Err  g    p\n\"xco2_6220\"  -0.00903  0.00025   15   4";
+        let adcfs = parse_adcf_table(table).unwrap();
+        let adcf = &adcfs["xco2_6220"];
+        assert_eq!(adcf.adcf, -0.00903);
+        assert_eq!(adcf.err, 0.00025);
+        assert_eq!((adcf.g, adcf.p), (15, 4));
+    }
+
+    #[test]
+    fn durations_need_a_count_and_unit() {
+        assert_eq!(_parse_duration("90s"), Some(std::time::Duration::from_secs(90)));
+        assert_eq!(_parse_duration("15m"), Some(std::time::Duration::from_secs(900)));
+        assert_eq!(_parse_duration(" 24h "), Some(std::time::Duration::from_secs(86400)));
+        assert_eq!(_parse_duration("2d"), Some(std::time::Duration::from_secs(172800)));
+        assert_eq!(_parse_duration("24"), None);
+        assert_eq!(_parse_duration("h"), None);
+        assert_eq!(_parse_duration("1.5h"), None);
+        assert_eq!(_parse_duration("-1h"), None);
+        assert_eq!(_parse_duration(""), None);
+    }
+
+    #[test]
+    fn datetimes_parse_with_or_without_time_and_zone() {
+        let midnight = NaiveDate::from_ymd_opt(2020, 6, 1).unwrap().and_hms_opt(0, 0, 0).unwrap();
+        let noon = NaiveDate::from_ymd_opt(2020, 6, 1).unwrap().and_hms_opt(12, 30, 0).unwrap();
+        assert_eq!(_parse_datetime("2020-06-01"), Some(midnight));
+        assert_eq!(_parse_datetime("2020-06-01T12:30:00Z"), Some(noon));
+        assert_eq!(_parse_datetime("2020-06-01 12:30 UTC"), Some(noon));
+        assert_eq!(_parse_datetime("06/01/2020"), None);
+    }
+
+    #[test]
+    fn cf_time_units_give_seconds_per_unit_and_epoch() {
+        let epoch = NaiveDate::from_ymd_opt(1970, 1, 1).unwrap().and_hms_opt(0, 0, 0).unwrap();
+        assert_eq!(_parse_time_units("seconds since 1970-01-01 00:00:00"), Some((1.0, epoch)));
+        assert_eq!(_parse_time_units("days since 1970-01-01"), Some((86400.0, epoch)));
+        assert_eq!(_parse_time_units("hours since 1970-01-01T00:00:00Z"), Some((3600.0, epoch)));
+        assert_eq!(_parse_time_units("fortnights since 1970-01-01"), None);
+        assert_eq!(_parse_time_units("seconds"), None);
+        assert_eq!(_parse_time_units("seconds since the epoch"), None);
+    }
+
+    #[test]
+    fn tolerance_keys_match_in_order_of_precedence() {
+        let clargs = parse_clargs_from(vec!["check-phase2", "--tolerance", "co2=0.1", "--tolerance", "xco2=0.2",
+                                            "--tolerance", "xco2_6220_adcf=0.3", "--tolerance", "xch4=0.4", "x.nc"]);
+        // The whole variable name, then its gas prefix as written, then with the x added or stripped
+        assert_eq!(_comparison_epsilon("xco2_6220_adcf", &clargs), 0.3);
+        assert_eq!(_comparison_epsilon("xco2_6073_adcf", &clargs), 0.2);
+        assert_eq!(_comparison_epsilon("co2_6073_adcf", &clargs), 0.1);
+        assert_eq!(_comparison_epsilon("ch4_5938_adcf", &clargs), 0.4);
+        // Scale factors have the gas after the vsw_sf_ prefix
+        assert_eq!(_comparison_epsilon("vsw_sf_co2_6220", &clargs), 0.1);
+        assert_eq!(_comparison_epsilon("vsw_sf_ch4_5938", &clargs), 0.4);
+        assert_eq!(_comparison_epsilon("xn2o_aicf", &clargs), DEFAULT_EPSILON);
+    }
+
+    #[test]
+    fn table_diff_marks_added_removed_and_changed_entries() {
+        let fields = |entries: &[(&str, &[(&'static str, &str)])]| entries.iter()
+            .map(|(name, f)| (name.to_string(), f.iter().map(|(k, v)| (*k, v.to_string())).collect()))
+            .collect::<HashMap<String, Vec<(&'static str, String)>>>();
+        let embedded = fields(&[
+            ("h2o_4565", &[("state", "active"), ("sf", "1"), ("gases", "'h2o co2 ch4'")]),
+            ("luft_6146", &[("state", "active"), ("sf", "1"), ("gases", "'luft'")]),
+            ("hcl_5790", &[("state", "commented out")]),
+            ("co_4290", &[("state", "active"), ("sf", "1"), ("gases", "'co'")])
+        ]);
+        let external = fields(&[
+            ("h2o_4565", &[("state", "active"), ("sf", "1"), ("gases", "'h2o co2'")]),
+            ("luft_6146", &[("state", "commented out")]),
+            ("hcl_5790", &[("state", "commented out")]),
+            ("hf_4038", &[("state", "commented out")])
+        ]);
+        assert_eq!(_diff_table_entries(&embedded, &external), vec![
+            "- co_4290",
+            "~ h2o_4565: gases 'h2o co2 ch4' -> 'h2o co2'",
+            "+ hf_4038 (commented out)",
+            "~ luft_6146: state active -> commented out"
+        ]);
+        assert!(_diff_table_entries(&embedded, &embedded).is_empty());
     }
 }